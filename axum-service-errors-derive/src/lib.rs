@@ -0,0 +1,204 @@
+//! Derive macros for `axum-service-errors`.
+//!
+//! These are re-exported by `axum-service-errors` behind the `derive`
+//! feature and should not be used directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Field, Fields, Ident, LitInt, LitStr, Variant, parse_macro_input};
+
+/// Derive `From<Enum> for ServiceError<'static>` for an error enum.
+///
+/// Every variant needs an `#[error(code = .., status = .., message = "..")]`
+/// attribute. The error name defaults to the variant name in
+/// `SCREAMING_SNAKE_CASE` and can be overridden with `name = ".."`. Fields of
+/// a variant are bound as typed message arguments in declaration order with
+/// `bind_value`; fields marked `#[error(display)]` are bound with their
+/// `Display` string through `bind` instead.
+///
+/// ```ignore
+/// #[derive(ServiceErrorEnum)]
+/// enum UserError {
+///     #[error(code = 2001, status = 404, message = "User {0} not found")]
+///     NotFound(String),
+///     #[error(code = 2002, status = 403, message = "Address {0} is blocked")]
+///     Blocked(#[error(display)] std::net::IpAddr),
+/// }
+/// ```
+#[proc_macro_derive(ServiceErrorEnum, attributes(error))]
+pub fn derive_service_error_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The values of a variant's `#[error(..)]` attribute.
+struct ErrorAttr {
+    code: LitInt,
+    status: LitInt,
+    message: LitStr,
+    name: Option<LitStr>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "ServiceErrorEnum can only be derived for enums",
+        ));
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let arms = data
+        .variants
+        .iter()
+        .map(|variant| expand_variant(ident, variant))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<#ident #ty_generics>
+            for ::axum_service_errors::ServiceError<'static> #where_clause
+        {
+            fn from(value: #ident #ty_generics) -> Self {
+                match value {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+fn expand_variant(enum_ident: &Ident, variant: &Variant) -> syn::Result<TokenStream2> {
+    let attr = parse_error_attr(variant)?;
+    let variant_ident = &variant.ident;
+    let ErrorAttr {
+        code,
+        status,
+        message,
+        name,
+    } = attr;
+    let name = name.unwrap_or_else(|| {
+        LitStr::new(
+            &screaming_snake_case(&variant_ident.to_string()),
+            variant_ident.span(),
+        )
+    });
+
+    let binds = variant
+        .fields
+        .iter()
+        .map(is_display_field)
+        .collect::<syn::Result<Vec<_>>>()?
+        .into_iter()
+        .map(|display| {
+            if display {
+                format_ident!("bind")
+            } else {
+                format_ident!("bind_value")
+            }
+        });
+
+    let (pattern, bindings) = match &variant.fields {
+        Fields::Unit => (quote!(#enum_ident::#variant_ident), Vec::new()),
+        Fields::Unnamed(fields) => {
+            let bindings: Vec<_> = (0..fields.unnamed.len())
+                .map(|i| format_ident!("field_{}", i))
+                .collect();
+            (
+                quote!(#enum_ident::#variant_ident(#(#bindings),*)),
+                bindings,
+            )
+        }
+        Fields::Named(fields) => {
+            let bindings: Vec<_> = fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .collect();
+            (
+                quote!(#enum_ident::#variant_ident { #(#bindings),* }),
+                bindings,
+            )
+        }
+    };
+
+    Ok(quote! {
+        #pattern => ::axum_service_errors::ServiceError::new(#code, #name, #status, #message)
+            #(.#binds(#bindings))*,
+    })
+}
+
+fn parse_error_attr(variant: &Variant) -> syn::Result<ErrorAttr> {
+    let attr = variant
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("error"))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &variant.ident,
+                "missing `#[error(code = .., status = .., message = \"..\")]` attribute",
+            )
+        })?;
+
+    let mut code = None;
+    let mut status = None;
+    let mut message = None;
+    let mut name = None;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("code") {
+            code = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("status") {
+            status = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("message") {
+            message = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("unknown error attribute"));
+        }
+        Ok(())
+    })?;
+
+    let missing = |key: &str| syn::Error::new_spanned(attr, format!("missing `{}`", key));
+    Ok(ErrorAttr {
+        code: code.ok_or_else(|| missing("code"))?,
+        status: status.ok_or_else(|| missing("status"))?,
+        message: message.ok_or_else(|| missing("message"))?,
+        name,
+    })
+}
+
+/// Whether a field is marked `#[error(display)]`.
+fn is_display_field(field: &Field) -> syn::Result<bool> {
+    let mut display = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("error"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("display") {
+                display = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown field attribute, expected `display`"))
+            }
+        })?;
+    }
+    Ok(display)
+}
+
+/// Convert a `CamelCase` identifier into `SCREAMING_SNAKE_CASE`.
+fn screaming_snake_case(ident: &str) -> String {
+    let mut output = String::with_capacity(ident.len() + 4);
+    for (i, c) in ident.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            output.push('_');
+        }
+        output.extend(c.to_uppercase());
+    }
+    output
+}
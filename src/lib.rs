@@ -1,27 +1,72 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, hash_map::Entry};
 use std::fmt::{Display, Formatter};
-use std::sync::OnceLock;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::SystemTime;
 
 use axum::{
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header, request::Parts},
     response::{IntoResponse, Response},
 };
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "derive")]
+pub use axum_service_errors_derive::ServiceErrorEnum;
+
 /// A parameter value that can be nested and supports various data types.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ParameterValue {
     String(String),
+    /// A UTC timestamp, serialized as an RFC 3339 string.
+    ///
+    /// Never produced by deserialization, so that enabling the `chrono`
+    /// feature does not change how strings deserialize: RFC 3339 strings
+    /// deserialize as [`ParameterValue::String`] and can be converted with
+    /// [`ParameterValue::parse_datetimes`].
+    #[cfg(feature = "chrono")]
+    #[serde(skip_deserializing)]
+    DateTime(chrono::DateTime<chrono::Utc>),
     Integer(i64),
     Float(f64),
     Boolean(bool),
+    /// Binary data, serialized as a base64 string.
+    ///
+    /// Never produced by deserialization: base64 strings deserialize as
+    /// [`ParameterValue::String`].
+    #[serde(serialize_with = "serialize_base64", skip_deserializing)]
+    Bytes(Vec<u8>),
     Array(Vec<ParameterValue>),
     Object(HashMap<String, ParameterValue>),
     Null,
 }
 
+fn serialize_base64<S: serde::Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&encode_base64(bytes))
+}
+
+/// Encode bytes as standard, padded base64.
+fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | (byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - 6 * i)) as usize & 0x3f] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
 impl From<String> for ParameterValue {
     fn from(value: String) -> Self {
         ParameterValue::String(value)
@@ -40,6 +85,55 @@ impl From<i32> for ParameterValue {
     }
 }
 
+impl From<i8> for ParameterValue {
+    fn from(value: i8) -> Self {
+        ParameterValue::Integer(value as i64)
+    }
+}
+
+impl From<i16> for ParameterValue {
+    fn from(value: i16) -> Self {
+        ParameterValue::Integer(value as i64)
+    }
+}
+
+impl From<u16> for ParameterValue {
+    fn from(value: u16) -> Self {
+        ParameterValue::Integer(value as i64)
+    }
+}
+
+impl From<u32> for ParameterValue {
+    fn from(value: u32) -> Self {
+        ParameterValue::Integer(value as i64)
+    }
+}
+
+/// Values above `i64::MAX` become a [`ParameterValue::String`] holding the
+/// exact decimal digits.
+impl From<u64> for ParameterValue {
+    fn from(value: u64) -> Self {
+        i64::try_from(value).map_or_else(
+            |_| ParameterValue::String(value.to_string()),
+            ParameterValue::Integer,
+        )
+    }
+}
+
+/// Values above `i64::MAX` become a [`ParameterValue::String`] holding the
+/// exact decimal digits.
+impl From<usize> for ParameterValue {
+    fn from(value: usize) -> Self {
+        ParameterValue::from(value as u64)
+    }
+}
+
+impl From<char> for ParameterValue {
+    fn from(value: char) -> Self {
+        ParameterValue::String(value.to_string())
+    }
+}
+
 impl From<i64> for ParameterValue {
     fn from(value: i64) -> Self {
         ParameterValue::Integer(value)
@@ -64,6 +158,13 @@ impl From<bool> for ParameterValue {
     }
 }
 
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for ParameterValue {
+    fn from(value: chrono::DateTime<chrono::Utc>) -> Self {
+        ParameterValue::DateTime(value)
+    }
+}
+
 impl From<Vec<ParameterValue>> for ParameterValue {
     fn from(value: Vec<ParameterValue>) -> Self {
         ParameterValue::Array(value)
@@ -76,6 +177,21 @@ impl From<HashMap<String, ParameterValue>> for ParameterValue {
     }
 }
 
+impl From<HashMap<String, String>> for ParameterValue {
+    fn from(value: HashMap<String, String>) -> Self {
+        ParameterValue::Object(value.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
+impl<T> From<Option<T>> for ParameterValue
+where
+    T: Into<ParameterValue>,
+{
+    fn from(value: Option<T>) -> Self {
+        value.map_or(ParameterValue::Null, |v| v.into())
+    }
+}
+
 impl From<Vec<String>> for ParameterValue {
     fn from(value: Vec<String>) -> Self {
         ParameterValue::Array(value.into_iter().map(|v| v.into()).collect())
@@ -106,6 +222,18 @@ impl From<Vec<f64>> for ParameterValue {
     }
 }
 
+impl From<Vec<u8>> for ParameterValue {
+    fn from(value: Vec<u8>) -> Self {
+        ParameterValue::Bytes(value)
+    }
+}
+
+impl From<&[u8]> for ParameterValue {
+    fn from(value: &[u8]) -> Self {
+        ParameterValue::Bytes(value.to_vec())
+    }
+}
+
 impl From<Vec<bool>> for ParameterValue {
     fn from(value: Vec<bool>) -> Self {
         ParameterValue::Array(value.into_iter().map(|v| v.into()).collect())
@@ -218,6 +346,12 @@ pub struct ObjectBuilder {
     map: HashMap<String, ParameterValue>,
 }
 
+impl Default for ObjectBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ObjectBuilder {
     pub fn new() -> Self {
         Self {
@@ -245,6 +379,12 @@ pub struct ArrayBuilder {
     items: Vec<ParameterValue>,
 }
 
+impl Default for ArrayBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ArrayBuilder {
     pub fn new() -> Self {
         Self {
@@ -287,20 +427,296 @@ macro_rules! param_array {
     };
 }
 
+/// Floats are hashed by their bit pattern, with `-0.0` normalized to `0.0`
+/// and every NaN to a single NaN, so that values which compare equal hash
+/// equally. Object entries are hashed in key order.
+impl Hash for ParameterValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            #[cfg(feature = "chrono")]
+            ParameterValue::DateTime(dt) => dt.hash(state),
+            ParameterValue::String(s) => s.hash(state),
+            ParameterValue::Integer(i) => i.hash(state),
+            ParameterValue::Float(f) => float_bits(*f).hash(state),
+            ParameterValue::Boolean(b) => b.hash(state),
+            ParameterValue::Bytes(bytes) => bytes.hash(state),
+            ParameterValue::Array(items) => items.hash(state),
+            ParameterValue::Object(entries) => hash_entries(entries, state),
+            ParameterValue::Null => {}
+        }
+    }
+}
+
+/// Index into an object by key.
+///
+/// # Panics
+///
+/// Panics if the value is not an object or has no entry for `key`. Use
+/// [`ParameterValue::get`] for a non-panicking lookup.
+impl std::ops::Index<&str> for ParameterValue {
+    type Output = ParameterValue;
+
+    fn index(&self, key: &str) -> &Self::Output {
+        match self {
+            ParameterValue::Object(entries) => entries
+                .get(key)
+                .unwrap_or_else(|| panic!("no entry found for key `{}`", key)),
+            other => panic!("cannot index {} with key `{}`", other.kind(), key),
+        }
+    }
+}
+
+/// Index into an array by position.
+///
+/// # Panics
+///
+/// Panics if the value is not an array or `index` is out of bounds. Use
+/// [`ParameterValue::get_index`] for a non-panicking lookup.
+impl std::ops::Index<usize> for ParameterValue {
+    type Output = ParameterValue;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self {
+            ParameterValue::Array(items) => items.get(index).unwrap_or_else(|| {
+                panic!(
+                    "index {} out of bounds for array of length {}",
+                    index,
+                    items.len()
+                )
+            }),
+            other => panic!("cannot index {} with index {}", other.kind(), index),
+        }
+    }
+}
+
+/// Hash map entries independently of their iteration order.
+/// The bit pattern of `f` with `-0.0` normalized to `0.0` and every NaN to
+/// a single NaN.
+fn float_bits(f: f64) -> u64 {
+    if f.is_nan() {
+        f64::NAN.to_bits()
+    } else if f == 0.0 {
+        0
+    } else {
+        f.to_bits()
+    }
+}
+
+/// Equality that compares floats by [`float_bits`], so that it is reflexive
+/// for NaN and consistent with the `Hash` impl.
+fn total_eq(a: &ParameterValue, b: &ParameterValue) -> bool {
+    match (a, b) {
+        (ParameterValue::Float(a), ParameterValue::Float(b)) => float_bits(*a) == float_bits(*b),
+        (ParameterValue::Array(a), ParameterValue::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| total_eq(a, b))
+        }
+        (ParameterValue::Object(a), ParameterValue::Object(b)) => entries_eq(a, b),
+        _ => a == b,
+    }
+}
+
+fn entries_eq(a: &HashMap<String, ParameterValue>, b: &HashMap<String, ParameterValue>) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .all(|(key, value)| b.get(key).is_some_and(|other| total_eq(value, other)))
+}
+
+fn hash_entries<H: Hasher>(entries: &HashMap<String, ParameterValue>, state: &mut H) {
+    let mut sorted: Vec<_> = entries.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(b.0));
+    sorted.len().hash(state);
+    for (key, value) in sorted {
+        key.hash(state);
+        value.hash(state);
+    }
+}
+
+/// Formats the value in a compact, human-readable form.
+///
+/// With the alternate flag (`{:#}`) the value is formatted as valid JSON
+/// instead: strings and object keys are quoted and escaped, and non-finite
+/// floats are written as `null`.
 impl Display for ParameterValue {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return self.fmt_json(f);
+        }
+        self.fmt_plain(f, PlainStyle::default())
+    }
+}
+
+/// How a builder renders [`ParameterValue::Bytes`] parameters.
+///
+/// The plain text builder defaults to [`BytesEncoding::Hex`], the JSON and
+/// YAML builders to [`BytesEncoding::Base64`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BytesEncoding {
+    /// Standard, padded base64, e.g. `aGVsbG8=`.
+    #[default]
+    Base64,
+    /// Lowercase hexadecimal, e.g. `68656c6c6f`.
+    Hex,
+}
+
+impl BytesEncoding {
+    /// Encode `bytes`, e.g. as `aGVsbG8=` or `68656c6c6f`.
+    fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            BytesEncoding::Base64 => encode_base64(bytes),
+            BytesEncoding::Hex => bytes.iter().map(|byte| format!("{:02x}", byte)).collect(),
+        }
+    }
+
+    /// The prefix of the plain display form, e.g. `base64:`.
+    fn plain_prefix(self) -> &'static str {
+        match self {
+            BytesEncoding::Base64 => "base64:",
+            BytesEncoding::Hex => "hex:",
+        }
+    }
+}
+
+/// Options for the plain display form of parameters.
+#[derive(Debug, Clone, Copy, Default)]
+struct PlainStyle {
+    /// Sort object keys lexicographically at every level.
+    sorted_keys: bool,
+    /// Annotate booleans and nulls as `(bool)true` and `(null)`, so they
+    /// can't be confused with the strings `"true"` or `"null"`.
+    typed_values: bool,
+    /// Encoding of binary values.
+    bytes: BytesEncoding,
+}
+
+impl ParameterValue {
+    /// Write the plain display form in the given style.
+    fn fmt_plain(&self, w: &mut dyn std::fmt::Write, style: PlainStyle) -> std::fmt::Result {
+        match self {
+            #[cfg(feature = "chrono")]
+            ParameterValue::DateTime(dt) => write!(w, "{}", format_rfc3339(dt)),
+            ParameterValue::String(s) => write!(w, "{}", s),
+            ParameterValue::Integer(i) => write!(w, "{}", i),
+            ParameterValue::Float(float) => write!(w, "{}", float),
+            ParameterValue::Boolean(b) if style.typed_values => write!(w, "(bool){}", b),
+            ParameterValue::Boolean(b) => write!(w, "{}", b),
+            ParameterValue::Bytes(bytes) => {
+                w.write_str(style.bytes.plain_prefix())?;
+                w.write_str(&style.bytes.encode(bytes))
+            }
+            ParameterValue::Array(arr) => {
+                write!(w, "[")?;
+                for (i, item) in arr.iter().enumerate() {
+                    if i > 0 {
+                        write!(w, ", ")?;
+                    }
+                    item.fmt_nested(w, style)?;
+                }
+                write!(w, "]")
+            }
+            ParameterValue::Object(obj) => write_plain_entries(w, obj, style),
+            ParameterValue::Null if style.typed_values => write!(w, "(null)"),
+            ParameterValue::Null => write!(w, "null"),
+        }
+    }
+
+    /// Write the plain display form of a value nested in a container.
+    ///
+    /// Strings containing delimiters are quoted so that the output stays
+    /// unambiguous; everything else matches [`Display`].
+    fn fmt_nested(&self, w: &mut dyn std::fmt::Write, style: PlainStyle) -> std::fmt::Result {
+        match self {
+            ParameterValue::String(s) => write_plain_str(w, s),
+            other => other.fmt_plain(w, style),
+        }
+    }
+}
+
+/// Write object entries in plain form as `{key: value, ...}`.
+fn write_plain_entries(
+    w: &mut dyn std::fmt::Write,
+    entries: &HashMap<String, ParameterValue>,
+    style: PlainStyle,
+) -> std::fmt::Result {
+    write!(w, "{{")?;
+    for (i, (key, value)) in object_entries(entries, style.sorted_keys)
+        .into_iter()
+        .enumerate()
+    {
+        if i > 0 {
+            write!(w, ", ")?;
+        }
+        write_plain_str(w, key)?;
+        write!(w, ": ")?;
+        value.fmt_nested(w, style)?;
+    }
+    write!(w, "}}")
+}
+
+/// Collect the entries of an object, sorted by key if requested.
+fn object_entries(
+    entries: &HashMap<String, ParameterValue>,
+    sorted: bool,
+) -> Vec<(&String, &ParameterValue)> {
+    let mut entries: Vec<_> = entries.iter().collect();
+    if sorted {
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+    }
+    entries
+}
+
+/// Write a key or string for plain container output, quoting and escaping
+/// it as a JSON string if it contains a delimiter or quote character.
+fn write_plain_str(w: &mut dyn std::fmt::Write, value: &str) -> std::fmt::Result {
+    if value.contains([':', ',', '"']) {
+        write_json_string(w, value)
+    } else {
+        w.write_str(value)
+    }
+}
+
+/// Format a timestamp as RFC 3339, matching its serialized form.
+#[cfg(feature = "chrono")]
+fn format_rfc3339(value: &chrono::DateTime<chrono::Utc>) -> String {
+    value.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+}
+
+/// Write a string as a quoted and escaped JSON string literal.
+fn write_json_string(f: &mut dyn std::fmt::Write, value: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl ParameterValue {
+    /// Format the value as JSON.
+    fn fmt_json(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            ParameterValue::String(s) => write!(f, "{}", s),
+            #[cfg(feature = "chrono")]
+            ParameterValue::DateTime(dt) => write_json_string(f, &format_rfc3339(dt)),
+            ParameterValue::String(s) => write_json_string(f, s),
             ParameterValue::Integer(i) => write!(f, "{}", i),
-            ParameterValue::Float(float) => write!(f, "{}", float),
+            ParameterValue::Float(float) if float.is_finite() => write!(f, "{}", float),
+            ParameterValue::Float(_) => write!(f, "null"),
             ParameterValue::Boolean(b) => write!(f, "{}", b),
+            ParameterValue::Bytes(bytes) => write_json_string(f, &encode_base64(bytes)),
             ParameterValue::Array(arr) => {
                 write!(f, "[")?;
                 for (i, item) in arr.iter().enumerate() {
                     if i > 0 {
                         write!(f, ", ")?;
                     }
-                    write!(f, "{}", item)?;
+                    item.fmt_json(f)?;
                 }
                 write!(f, "]")
             }
@@ -310,16 +726,16 @@ impl Display for ParameterValue {
                     if i > 0 {
                         write!(f, ", ")?;
                     }
-                    write!(f, "{}: {}", key, value)?;
+                    write_json_string(f, key)?;
+                    write!(f, ": ")?;
+                    value.fmt_json(f)?;
                 }
                 write!(f, "}}")
             }
             ParameterValue::Null => write!(f, "null"),
         }
     }
-}
 
-impl ParameterValue {
     /// Create a new array parameter value.
     pub fn array(items: Vec<impl Into<ParameterValue>>) -> Self {
         ParameterValue::Array(items.into_iter().map(|v| v.into()).collect())
@@ -329,245 +745,3548 @@ impl ParameterValue {
     pub fn object(map: impl Into<HashMap<String, ParameterValue>>) -> Self {
         ParameterValue::Object(map.into())
     }
-}
 
-/// A trait for building custom response formats from ServiceError data.
-pub trait ResponseBuilder: std::fmt::Debug + Send + Sync {
-    /// Build a response body and content-type from the error data.
-    fn build(&self, error: &ServiceError) -> (String, &'static str);
-}
+    /// Get the number of items in an array or entries in an object.
+    ///
+    /// Scalars (including strings) return `None`.
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            ParameterValue::Array(items) => Some(items.len()),
+            ParameterValue::Object(entries) => Some(entries.len()),
+            _ => None,
+        }
+    }
 
-/// Global default response builder storage.
-static DEFAULT_RESPONSE_BUILDER: OnceLock<Box<dyn ResponseBuilder>> = OnceLock::new();
+    /// Returns `true` if this is an array or object without any elements.
+    ///
+    /// Scalars are never considered empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
 
-/// Set the global default response builder for all ServiceError instances.
-/// This should be called once at application startup.
-pub fn set_default_response_builder(builder: impl ResponseBuilder + 'static) {
-    DEFAULT_RESPONSE_BUILDER.set(Box::new(builder)).ok();
-}
+    /// Iterate over the items of an array, or `None` if this is not an array.
+    pub fn iter_array(&self) -> Option<impl Iterator<Item = &ParameterValue>> {
+        match self {
+            ParameterValue::Array(items) => Some(items.iter()),
+            _ => None,
+        }
+    }
 
-/// Get the global default response builder, if one has been set.
-fn get_default_response_builder() -> Option<&'static Box<dyn ResponseBuilder>> {
-    DEFAULT_RESPONSE_BUILDER.get()
-}
+    /// Iterate over the entries of an object, or `None` if this is not an
+    /// object.
+    pub fn iter_object(&self) -> Option<impl Iterator<Item = (&String, &ParameterValue)>> {
+        match self {
+            ParameterValue::Object(entries) => Some(entries.iter()),
+            _ => None,
+        }
+    }
 
-/// A `ServiceError` represents a specific error within the software.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ServiceError<'a> {
-    /// An internal error code that represents a specific error within the
-    /// system.
-    pub code: u32,
-    /// A capitalized error name that represents the error type.
-    #[serde(borrow)]
-    pub name: Cow<'a, str>,
-    /// The respective HTTP status code that should be returned to the client.
-    #[serde(skip)]
-    pub http_status: u16,
-    /// A human-readable error message that describes the error in more detail.
-    #[serde(borrow)]
-    pub message: Cow<'a, str>,
-    /// Arguments for message formatting
-    #[serde(skip)]
-    pub arguments: Vec<String>,
-    /// Optional parameters as key-value pairs
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub parameters: Option<HashMap<String, ParameterValue>>,
-    /// Custom response builder for formatting output
-    #[serde(skip)]
-    response_builder: Option<Box<dyn ResponseBuilder>>,
-}
+    /// Get the value stored under `key`, or `None` if this is not an object
+    /// or the key is missing.
+    pub fn get(&self, key: &str) -> Option<&ParameterValue> {
+        match self {
+            ParameterValue::Object(entries) => entries.get(key),
+            _ => None,
+        }
+    }
 
-impl<'a> Clone for ServiceError<'a> {
-    fn clone(&self) -> Self {
-        Self {
-            code: self.code,
-            name: self.name.clone(),
-            http_status: self.http_status,
-            message: self.message.clone(),
-            arguments: self.arguments.clone(),
-            parameters: self.parameters.clone(),
-            response_builder: None, // Cannot clone trait objects
+    /// Get the element at `index`, or `None` if this is not an array or the
+    /// index is out of bounds.
+    pub fn get_index(&self, index: usize) -> Option<&ParameterValue> {
+        match self {
+            ParameterValue::Array(items) => items.get(index),
+            _ => None,
         }
     }
-}
 
-impl<'a> ServiceError<'a> {
-    /// Create a new [`ServiceError`] instance.
-    pub const fn new(code: u32, name: &'a str, status: u16, message: &'a str) -> Self {
-        Self {
-            code,
-            name: Cow::Borrowed(name),
-            http_status: status,
-            message: Cow::Borrowed(message),
-            arguments: Vec::new(),
-            parameters: None,
-            response_builder: None,
+    /// Deep-merge `other` into this value.
+    ///
+    /// When both values are objects, entries of `other` are merged in
+    /// recursively, with `other` winning on conflicting non-object values.
+    /// In every other case, including arrays, `self` is replaced by `other`
+    /// wholesale; arrays are never concatenated.
+    pub fn merge(&mut self, other: ParameterValue) {
+        match (self, other) {
+            (ParameterValue::Object(entries), ParameterValue::Object(other_entries)) => {
+                for (key, value) in other_entries {
+                    match entries.get_mut(&key) {
+                        Some(existing) => existing.merge(value),
+                        None => {
+                            entries.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (this, other) => *this = other,
         }
     }
 
-    /// Add an argument for message formatting.
-    pub fn bind(mut self, value: impl ToString) -> Self {
-        self.arguments.push(value.to_string());
-        self
+    /// Parse an untyped string into the most specific scalar value.
+    ///
+    /// `"true"` and `"false"` become [`ParameterValue::Boolean`], `"null"`
+    /// becomes [`ParameterValue::Null`] and numbers are recognized as by
+    /// [`ParameterValue::coerce_numbers`]. Anything else is kept as a
+    /// [`ParameterValue::String`].
+    pub fn parse(s: &str) -> ParameterValue {
+        match s {
+            "true" => ParameterValue::Boolean(true),
+            "false" => ParameterValue::Boolean(false),
+            "null" => ParameterValue::Null,
+            _ => {
+                let mut value = ParameterValue::from(s);
+                value.coerce_numbers();
+                value
+            }
+        }
     }
 
-    /// Add an optional parameter.
-    pub fn parameter(mut self, key: impl ToString, value: impl Into<ParameterValue>) -> Self {
-        let parameters = self.parameters.get_or_insert_with(HashMap::new);
-        parameters.insert(key.to_string(), value.into());
-        self
+    /// Parse a JSON literal, including arrays and objects, into a value.
+    #[cfg(feature = "json")]
+    pub fn from_json_str(s: &str) -> Result<ParameterValue, serde_json::Error> {
+        serde_json::from_str(s)
     }
 
-    /// Add multiple parameters at once.
-    pub fn parameters<K, V, I>(mut self, params: I) -> Self
-    where
-        K: Into<String>,
-        V: Into<ParameterValue>,
-        I: IntoIterator<Item = (K, V)>,
-    {
-        let parameters = self.parameters.get_or_insert_with(HashMap::new);
-        for (key, value) in params {
-            parameters.insert(key.into(), value.into());
+    /// Convert strings that parse cleanly as numbers into numeric values,
+    /// descending into arrays and objects.
+    ///
+    /// Strings that parse as `i64` become [`ParameterValue::Integer`], other
+    /// finite decimal numbers become [`ParameterValue::Float`]. All other
+    /// values, including `"NaN"` and `"inf"`, are left untouched.
+    pub fn coerce_numbers(&mut self) {
+        match self {
+            ParameterValue::String(s) => {
+                if let Ok(number) = s.parse::<i64>() {
+                    *self = ParameterValue::Integer(number);
+                } else if s
+                    .bytes()
+                    .all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b))
+                    && let Ok(number) = s.parse::<f64>()
+                    && number.is_finite()
+                {
+                    *self = ParameterValue::Float(number);
+                }
+            }
+            ParameterValue::Array(items) => items.iter_mut().for_each(Self::coerce_numbers),
+            ParameterValue::Object(entries) => entries.values_mut().for_each(Self::coerce_numbers),
+            _ => {}
         }
-        self
     }
 
-    /// Set a custom response builder for formatting the response.
-    pub fn with_response_builder(mut self, builder: impl ResponseBuilder + 'static) -> Self {
-        self.response_builder = Some(Box::new(builder));
-        self
+    /// Convert every string that is a valid RFC 3339 timestamp, at any
+    /// nesting level, into a [`ParameterValue::DateTime`].
+    ///
+    /// Use this to opt into timestamps after deserializing, e.g.
+    /// `serde_json::from_str::<ParameterValue>(json)?.parse_datetimes()`.
+    #[cfg(feature = "chrono")]
+    pub fn parse_datetimes(self) -> ParameterValue {
+        self.transform(&mut |value| match value {
+            ParameterValue::String(s) => match chrono::DateTime::parse_from_rfc3339(&s) {
+                Ok(dt) => ParameterValue::DateTime(dt.with_timezone(&chrono::Utc)),
+                Err(_) => ParameterValue::String(s),
+            },
+            other => other,
+        })
     }
 
-    /// Format the message with provided arguments.
-    fn format_message(&self) -> String {
-        let mut formatted = self.message.to_string();
-        for (i, arg) in self.arguments.iter().enumerate() {
-            let placeholder = format!("{{{i}}}");
-            formatted = formatted.replace(&placeholder, arg);
-        }
-        formatted
+    /// Rewrite the value tree by applying `f` to every node, bottom-up.
+    ///
+    /// Children are transformed before their parent: array items in order,
+    /// then object values in unspecified order, and finally the array or
+    /// object itself, which `f` receives with its already transformed
+    /// children. Scalars are passed to `f` directly.
+    pub fn transform(self, f: &mut impl FnMut(ParameterValue) -> ParameterValue) -> ParameterValue {
+        let value = match self {
+            ParameterValue::Array(items) => {
+                ParameterValue::Array(items.into_iter().map(|item| item.transform(f)).collect())
+            }
+            ParameterValue::Object(entries) => ParameterValue::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, value.transform(f)))
+                    .collect(),
+            ),
+            other => other,
+        };
+        f(value)
     }
-}
 
-impl<'a> IntoResponse for ServiceError<'a> {
-    fn into_response(self) -> Response {
+    /// Compare structurally like `==`, but treat floats as equal if they
+    /// differ by at most `epsilon`, at any nesting level.
+    ///
+    /// Integers and floats never compare equal to each other, matching
+    /// `==`. Mainly useful in tests.
+    pub fn approx_eq(&self, other: &ParameterValue, epsilon: f64) -> bool {
+        match (self, other) {
+            (ParameterValue::Float(a), ParameterValue::Float(b)) => {
+                a == b || (a - b).abs() <= epsilon
+            }
+            (ParameterValue::Array(a), ParameterValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.approx_eq(b, epsilon))
+            }
+            (ParameterValue::Object(a), ParameterValue::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(key, a)| b.get(key).is_some_and(|b| a.approx_eq(b, epsilon)))
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Name of the variant, used in conversion errors.
+    fn kind(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "chrono")]
+            ParameterValue::DateTime(_) => "DateTime",
+            ParameterValue::String(_) => "String",
+            ParameterValue::Integer(_) => "Integer",
+            ParameterValue::Float(_) => "Float",
+            ParameterValue::Boolean(_) => "Boolean",
+            ParameterValue::Bytes(_) => "Bytes",
+            ParameterValue::Array(_) => "Array",
+            ParameterValue::Object(_) => "Object",
+            ParameterValue::Null => "Null",
+        }
+    }
+}
+
+/// A [`ParameterValue`] could not be converted because it holds a different
+/// variant than the target type expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParameterTypeMismatch {
+    /// The variant the conversion expected.
+    pub expected: &'static str,
+    /// The variant the value actually holds.
+    pub actual: &'static str,
+}
+
+impl Display for ParameterTypeMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.actual)
+    }
+}
+
+impl std::error::Error for ParameterTypeMismatch {}
+
+impl TryFrom<ParameterValue> for String {
+    type Error = ParameterTypeMismatch;
+
+    fn try_from(value: ParameterValue) -> Result<Self, Self::Error> {
+        match value {
+            ParameterValue::String(s) => Ok(s),
+            other => Err(ParameterTypeMismatch {
+                expected: "String",
+                actual: other.kind(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<ParameterValue> for i64 {
+    type Error = ParameterTypeMismatch;
+
+    fn try_from(value: ParameterValue) -> Result<Self, Self::Error> {
+        match value {
+            ParameterValue::Integer(i) => Ok(i),
+            other => Err(ParameterTypeMismatch {
+                expected: "Integer",
+                actual: other.kind(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<ParameterValue> for f64 {
+    type Error = ParameterTypeMismatch;
+
+    fn try_from(value: ParameterValue) -> Result<Self, Self::Error> {
+        match value {
+            ParameterValue::Float(f) => Ok(f),
+            other => Err(ParameterTypeMismatch {
+                expected: "Float",
+                actual: other.kind(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<ParameterValue> for bool {
+    type Error = ParameterTypeMismatch;
+
+    fn try_from(value: ParameterValue) -> Result<Self, Self::Error> {
+        match value {
+            ParameterValue::Boolean(b) => Ok(b),
+            other => Err(ParameterTypeMismatch {
+                expected: "Boolean",
+                actual: other.kind(),
+            }),
+        }
+    }
+}
+
+/// A trait for building custom response formats from ServiceError data.
+pub trait ResponseBuilder: std::fmt::Debug + Send + Sync {
+    /// Build a response body and content-type from the error data.
+    fn build(&self, error: &ServiceError) -> (String, &'static str);
+
+    /// Build a response body and content-type from the error data and the
+    /// parts of the request being answered.
+    ///
+    /// Called by [`ServiceError::into_response_with_parts`]. The default
+    /// implementation ignores the request and calls
+    /// [`ResponseBuilder::build`].
+    fn build_with_parts(&self, error: &ServiceError, parts: &Parts) -> (String, &'static str) {
+        let _ = parts;
+        self.build(error)
+    }
+
+    /// Return the content-type of the responses this builder produces.
+    ///
+    /// The default implementation builds and discards a response for a
+    /// default error. Builders with a fixed content-type should override it.
+    fn content_type(&self) -> &'static str {
+        self.build(&ServiceError::default()).1
+    }
+
+    /// Write the response body into `w` and return the content-type.
+    ///
+    /// The default implementation writes the output of
+    /// [`ResponseBuilder::build`]. Builders can override it to write
+    /// directly without intermediate allocations, and implement `build` on
+    /// top of it.
+    fn write_to(
+        &self,
+        error: &ServiceError,
+        w: &mut dyn std::fmt::Write,
+    ) -> Result<&'static str, std::fmt::Error> {
+        let (body, content_type) = self.build(error);
+        w.write_str(&body)?;
+        Ok(content_type)
+    }
+}
+
+impl<B: ResponseBuilder + ?Sized> ResponseBuilder for Arc<B> {
+    fn build(&self, error: &ServiceError) -> (String, &'static str) {
+        (**self).build(error)
+    }
+
+    fn build_with_parts(&self, error: &ServiceError, parts: &Parts) -> (String, &'static str) {
+        (**self).build_with_parts(error, parts)
+    }
+
+    fn content_type(&self) -> &'static str {
+        (**self).content_type()
+    }
+
+    fn write_to(
+        &self,
+        error: &ServiceError,
+        w: &mut dyn std::fmt::Write,
+    ) -> Result<&'static str, std::fmt::Error> {
+        (**self).write_to(error, w)
+    }
+}
+
+/// Global default response builder storage.
+static DEFAULT_RESPONSE_BUILDER: RwLock<Option<Arc<dyn ResponseBuilder>>> = RwLock::new(None);
+
+/// The `charset` parameter sent in the content-type of the built-in
+/// builders, see e.g. [`PlainTextResponseBuilder::with_charset`].
+///
+/// Response bodies are always UTF-8, so that is the only charset that can be
+/// advertised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Charset {
+    /// `charset=utf-8`
+    #[default]
+    Utf8,
+}
+
+/// The media types produced by the built-in builders.
+#[derive(Debug, Clone, Copy)]
+enum MediaType {
+    Text,
+    Html,
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl MediaType {
+    /// The content-type, with a `charset` parameter if one is given.
+    const fn content_type(self, charset: Option<Charset>) -> &'static str {
+        match (self, charset) {
+            (MediaType::Text, None) => "text/plain",
+            (MediaType::Text, Some(Charset::Utf8)) => "text/plain; charset=utf-8",
+            (MediaType::Html, None) => "text/html",
+            (MediaType::Html, Some(Charset::Utf8)) => "text/html; charset=utf-8",
+            #[cfg(feature = "json")]
+            (MediaType::Json, None) => "application/json",
+            #[cfg(feature = "json")]
+            (MediaType::Json, Some(Charset::Utf8)) => "application/json; charset=utf-8",
+            #[cfg(feature = "yaml")]
+            (MediaType::Yaml, None) => "application/yaml",
+            #[cfg(feature = "yaml")]
+            (MediaType::Yaml, Some(Charset::Utf8)) => "application/yaml; charset=utf-8",
+        }
+    }
+}
+
+/// Set the global default response builder for all ServiceError instances.
+/// This should be called once at application startup.
+///
+/// Replaces any previously set builder and returns it.
+pub fn set_default_response_builder(
+    builder: impl ResponseBuilder + 'static,
+) -> Option<Arc<dyn ResponseBuilder>> {
+    DEFAULT_RESPONSE_BUILDER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .replace(Arc::new(builder))
+}
+
+/// Remove the global default response builder and return it.
+///
+/// Errors without their own builder fall back to the default response
+/// format again. Mainly useful to isolate tests that set a default.
+pub fn reset_default_response_builder() -> Option<Arc<dyn ResponseBuilder>> {
+    DEFAULT_RESPONSE_BUILDER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+}
+
+/// Get the global default response builder, if one has been set.
+fn get_default_response_builder() -> Option<Arc<dyn ResponseBuilder>> {
+    DEFAULT_RESPONSE_BUILDER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// The severity of a [`ServiceError`].
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    #[default]
+    Error,
+    Critical,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+            Severity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+/// A broad category of a [`ServiceError`], used to route logging and
+/// alerting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Validation,
+    Auth,
+    Forbidden,
+    NotFound,
+    Conflict,
+    RateLimited,
+    Internal,
+    Unavailable,
+}
+
+impl Category {
+    /// The HTTP status usually returned for errors of this category.
+    pub const fn default_status(&self) -> u16 {
+        match self {
+            Category::Validation => 400,
+            Category::Auth => 401,
+            Category::Forbidden => 403,
+            Category::NotFound => 404,
+            Category::Conflict => 409,
+            Category::RateLimited => 429,
+            Category::Internal => 500,
+            Category::Unavailable => 503,
+        }
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Category::Validation => write!(f, "validation"),
+            Category::Auth => write!(f, "auth"),
+            Category::Forbidden => write!(f, "forbidden"),
+            Category::NotFound => write!(f, "not_found"),
+            Category::Conflict => write!(f, "conflict"),
+            Category::RateLimited => write!(f, "rate_limited"),
+            Category::Internal => write!(f, "internal"),
+            Category::Unavailable => write!(f, "unavailable"),
+        }
+    }
+}
+
+/// A built-in response format used when no response builder is configured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResponseFormat {
+    /// Render errors with [`PlainTextResponseBuilder`].
+    #[default]
+    Plain,
+    /// Render errors with [`JsonResponseBuilder`].
+    #[cfg(feature = "json")]
+    Json,
+}
+
+impl ResponseFormat {
+    fn build(self, error: &ServiceError) -> (String, &'static str) {
+        match self {
+            ResponseFormat::Plain => PlainTextResponseBuilder::new().build(error),
+            #[cfg(feature = "json")]
+            ResponseFormat::Json => JsonResponseBuilder::new().build(error),
+        }
+    }
+}
+
+/// Global default response format storage.
+static DEFAULT_RESPONSE_FORMAT: RwLock<Option<ResponseFormat>> = RwLock::new(None);
+
+/// Set the built-in format used for ServiceError instances when neither the
+/// instance nor the global default response builder is set.
+/// This should be called once at application startup.
+///
+/// Replaces any previously set format and returns it.
+pub fn set_default_response_format(format: ResponseFormat) -> Option<ResponseFormat> {
+    DEFAULT_RESPONSE_FORMAT
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .replace(format)
+}
+
+/// Remove the global default response format and return it.
+///
+/// Errors without a builder are rendered as plain text again. Mainly useful
+/// to isolate tests that set a default.
+pub fn reset_default_response_format() -> Option<ResponseFormat> {
+    DEFAULT_RESPONSE_FORMAT
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+}
+
+/// Get the global default response format, falling back to plain text.
+fn get_default_response_format() -> ResponseFormat {
+    DEFAULT_RESPONSE_FORMAT
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .unwrap_or_default()
+}
+
+/// The value of a `Retry-After` response header, see
+/// [`ServiceError::rate_limited`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAfter {
+    /// Retry after the given number of seconds.
+    Seconds(u64),
+    /// Retry after the given point in time, sent as an RFC 7231
+    /// IMF-fixdate such as `Sun, 06 Nov 1994 08:49:37 GMT`.
+    HttpDate(SystemTime),
+}
+
+impl Display for RetryAfter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetryAfter::Seconds(seconds) => write!(f, "{}", seconds),
+            RetryAfter::HttpDate(time) => write_http_date(f, *time),
+        }
+    }
+}
+
+/// The number of local codes in each error code namespace.
+///
+/// A namespaced code is composed as `prefix * NAMESPACE_SIZE + code`, so
+/// prefix `42` and local code `1001` give `4201001`. Local codes must be
+/// below this size to be decoded back with [`namespace_of`].
+pub const NAMESPACE_SIZE: u32 = 100_000;
+
+/// Split a namespaced error code into its prefix and local code.
+///
+/// This reverses [`ServiceError::with_namespace`]: `namespace_of(4201001)`
+/// is `(42, 1001)`. Codes without a namespace have prefix `0`.
+pub const fn namespace_of(code: u32) -> (u32, u32) {
+    (code / NAMESPACE_SIZE, code % NAMESPACE_SIZE)
+}
+
+/// A `ServiceError` represents a specific error within the software.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceError<'a> {
+    /// An internal error code that represents a specific error within the
+    /// system.
+    pub code: u32,
+    /// A capitalized error name that represents the error type.
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+    /// Optional stable, machine-readable identifier such as
+    /// `"user.not_found"`, for clients to branch on.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<Cow<'a, str>>,
+    /// The respective HTTP status code that should be returned to the client.
+    #[serde(skip)]
+    pub http_status: u16,
+    /// A human-readable error message that describes the error in more detail.
+    #[serde(borrow)]
+    pub message: Cow<'a, str>,
+    /// Closure producing the message on render, taking precedence over
+    /// `message`
+    #[serde(skip)]
+    lazy_message: Option<LazyMessage>,
+    /// Optional longer explanation shown alongside the message
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<Cow<'a, str>>,
+    /// Note for engineers, included in logs and tracing but never in
+    /// rendered responses
+    #[serde(skip)]
+    pub internal_note: Option<String>,
+    /// Arguments for message formatting
+    #[serde(skip)]
+    pub arguments: Vec<ParameterValue>,
+    /// Optional parameters as key-value pairs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<HashMap<String, ParameterValue>>,
+    /// Parameters fixed at compile time, merged with `parameters` on render
+    #[serde(skip)]
+    static_parameters: &'static [(&'static str, &'static str)],
+    /// Optional request or trace id used to correlate the error with logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// Optional link to the documentation of this error, serialized as
+    /// `type` in line with RFC 7807
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub doc_url: Option<String>,
+    /// How severe the error is, used to classify and route errors
+    #[serde(default)]
+    pub severity: Severity,
+    /// Optional category of the error, used to route logging and alerting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<Category>,
+    /// Challenge sent in the `WWW-Authenticate` response header
+    #[serde(skip)]
+    pub www_authenticate: Option<String>,
+    /// Value of the `Retry-After` response header
+    #[serde(skip)]
+    pub retry_after: Option<RetryAfter>,
+    /// Value of the `Cache-Control` response header
+    #[serde(skip)]
+    pub cache_control: Option<String>,
+    /// When the error was generated, rendered as an RFC 3339 `timestamp`
+    #[serde(skip)]
+    pub timestamp: Option<SystemTime>,
+    /// Custom response builder for formatting output
+    #[serde(skip)]
+    response_builder: Option<Box<dyn ResponseBuilder>>,
+    /// Parameter keys whose values are hidden from rendered responses
+    #[serde(skip)]
+    redacted_keys: Vec<String>,
+    /// Parameter keys allowed in rendered responses, if restricted
+    #[serde(skip)]
+    public_keys: Option<Vec<String>>,
+    /// Whether server error messages are hidden from rendered responses
+    #[serde(skip)]
+    client_safe: bool,
+    /// Whether unresolved placeholders in the message are reported
+    #[serde(skip)]
+    strict_formatting: bool,
+    /// Whether configured response builders are bypassed when rendering
+    #[serde(skip)]
+    fallback_format: bool,
+    /// Whether server error responses are sent with `Cache-Control: no-store`
+    #[serde(skip)]
+    no_store_for_5xx: bool,
+    /// Underlying error that caused this error
+    #[serde(skip)]
+    source: Option<Arc<dyn std::error::Error + Send + Sync>>,
+}
+
+/// A result whose error is an owned [`ServiceError`], convenient as the
+/// return type of handlers, e.g. `ServiceResult<Json<User>>`.
+///
+/// Errors built from string literals can be returned with `?` directly.
+/// Errors borrowing from local data are converted with
+/// [`ServiceError::into_owned`] first, e.g.
+/// `.map_err(ServiceError::into_owned)?`.
+///
+/// # Clippy
+///
+/// A [`ServiceError`] carries everything needed to render the response and
+/// is larger than the 128 bytes at which `clippy::result_large_err` fires,
+/// so functions returning a `ServiceResult` trigger that lint. Errors are
+/// only built on the failure path, where the size does not matter, so
+/// allow the lint for handler modules with
+/// `#![allow(clippy::result_large_err)]` or raise `large-error-threshold`
+/// in `clippy.toml`.
+pub type ServiceResult<T> = Result<T, ServiceError<'static>>;
+
+impl<'a> Clone for ServiceError<'a> {
+    fn clone(&self) -> Self {
+        Self {
+            code: self.code,
+            name: self.name.clone(),
+            slug: self.slug.clone(),
+            http_status: self.http_status,
+            message: self.message.clone(),
+            lazy_message: self.lazy_message.clone(),
+            detail: self.detail.clone(),
+            internal_note: self.internal_note.clone(),
+            arguments: self.arguments.clone(),
+            parameters: self.parameters.clone(),
+            static_parameters: self.static_parameters,
+            trace_id: self.trace_id.clone(),
+            doc_url: self.doc_url.clone(),
+            severity: self.severity,
+            category: self.category,
+            www_authenticate: self.www_authenticate.clone(),
+            retry_after: self.retry_after,
+            cache_control: self.cache_control.clone(),
+            timestamp: self.timestamp,
+            response_builder: None, // Cannot clone trait objects
+            redacted_keys: self.redacted_keys.clone(),
+            public_keys: self.public_keys.clone(),
+            client_safe: self.client_safe,
+            strict_formatting: self.strict_formatting,
+            fallback_format: self.fallback_format,
+            no_store_for_5xx: self.no_store_for_5xx,
+            source: self.source.clone(),
+        }
+    }
+}
+
+/// Errors compare equal when their code, name, status, message, arguments
+/// and parameters match. Parameters are compared as they are rendered, with
+/// the [static parameters](ServiceError::with_static_parameters) merged in,
+/// and floats are compared by bit pattern so that an error holding `NaN` is
+/// equal to itself. Other fields, such as the response builder, are ignored.
+impl PartialEq for ServiceError<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code
+            && self.name == other.name
+            && self.http_status == other.http_status
+            && self.message == other.message
+            && self.arguments.len() == other.arguments.len()
+            && self
+                .arguments
+                .iter()
+                .zip(&other.arguments)
+                .all(|(a, b)| total_eq(a, b))
+            && match (self.all_parameters(), other.all_parameters()) {
+                (Some(a), Some(b)) => entries_eq(&a, &b),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+impl Eq for ServiceError<'_> {}
+
+impl Hash for ServiceError<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.code.hash(state);
+        self.name.hash(state);
+        self.http_status.hash(state);
+        self.message.hash(state);
+        self.arguments.hash(state);
+        match self.all_parameters() {
+            Some(parameters) => {
+                1u8.hash(state);
+                hash_entries(&parameters, state);
+            }
+            None => 0u8.hash(state),
+        }
+    }
+}
+
+/// A generic internal server error with code `5000`, name
+/// `"INTERNAL_ERROR"` and an empty message.
+impl Default for ServiceError<'_> {
+    fn default() -> Self {
+        Self::new(5000, "INTERNAL_ERROR", 500, "")
+    }
+}
+
+impl<'a> ServiceError<'a> {
+    /// Create a new [`ServiceError`] instance.
+    pub const fn new(code: u32, name: &'a str, status: u16, message: &'a str) -> Self {
+        Self {
+            code,
+            name: Cow::Borrowed(name),
+            slug: None,
+            http_status: status,
+            message: Cow::Borrowed(message),
+            lazy_message: None,
+            detail: None,
+            internal_note: None,
+            arguments: Vec::new(),
+            parameters: None,
+            static_parameters: &[],
+            trace_id: None,
+            doc_url: None,
+            severity: Severity::Error,
+            category: None,
+            www_authenticate: None,
+            retry_after: None,
+            cache_control: None,
+            timestamp: None,
+            response_builder: None,
+            redacted_keys: Vec::new(),
+            public_keys: None,
+            client_safe: false,
+            strict_formatting: false,
+            fallback_format: false,
+            no_store_for_5xx: false,
+            source: None,
+        }
+    }
+
+    /// Create a new [`ServiceError`] of the given category, using the
+    /// category's [default status](Category::default_status).
+    pub const fn of(category: Category, code: u32, name: &'a str, message: &'a str) -> Self {
+        let mut error = Self::new(code, name, category.default_status(), message);
+        error.category = Some(category);
+        error
+    }
+
+    /// Create a new [`ServiceError`] instance, validating the HTTP status.
+    ///
+    /// Unlike [`ServiceError::new`], which falls back to `500` when the
+    /// response is built, this fails if `status` is not a valid status code
+    /// within the 1xx-5xx classes defined by RFC 9110.
+    pub fn try_new(
+        code: u32,
+        name: &'a str,
+        status: u16,
+        message: &'a str,
+    ) -> Result<Self, InvalidStatus> {
+        match StatusCode::from_u16(status) {
+            Ok(_) if status < 600 => Ok(Self::new(code, name, status, message)),
+            _ => Err(InvalidStatus(status)),
+        }
+    }
+
+    /// Add an argument for message formatting, stored as its `Display`
+    /// string.
+    pub fn bind(mut self, value: impl ToString) -> Self {
+        self.arguments
+            .push(ParameterValue::String(value.to_string()));
+        self
+    }
+
+    /// Add a typed argument for message formatting.
+    ///
+    /// Unlike [`ServiceError::bind`] the argument keeps its type, so
+    /// numeric format specs such as `{0:02}` or `{0:.2}` apply to it.
+    pub fn bind_value(mut self, value: impl Into<ParameterValue>) -> Self {
+        self.arguments.push(value.into());
+        self
+    }
+
+    /// Add several arguments for message formatting, in order.
+    pub fn bind_all<I>(mut self, values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: ToString,
+    {
+        self.arguments.extend(
+            values
+                .into_iter()
+                .map(|value| ParameterValue::String(value.to_string())),
+        );
+        self
+    }
+
+    /// Add an optional parameter.
+    pub fn parameter(mut self, key: impl ToString, value: impl Into<ParameterValue>) -> Self {
+        let parameters = self.parameters.get_or_insert_with(HashMap::new);
+        parameters.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Attach parameters that are known at compile time.
+    ///
+    /// Unlike [`ServiceError::parameter`] this is `const` and does not
+    /// allocate, so fully-populated errors can be declared as constants.
+    /// The static parameters are merged with the runtime ones when the
+    /// error is rendered; a runtime parameter with the same key wins.
+    ///
+    /// ```
+    /// use axum_service_errors::ServiceError;
+    ///
+    /// const QUOTA_EXCEEDED: ServiceError<'static> =
+    ///     ServiceError::new(4290, "QUOTA_EXCEEDED", 429, "Quota exceeded")
+    ///         .with_static_parameters(&[("plan", "free")]);
+    /// ```
+    pub const fn with_static_parameters(
+        mut self,
+        parameters: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        self.static_parameters = parameters;
+        self
+    }
+
+    /// Append a value to the array parameter under `key`.
+    ///
+    /// A missing parameter is created as a single-element array. An
+    /// existing non-array value is not discarded: it becomes the first
+    /// element of the new array, followed by `value`.
+    pub fn push_parameter(mut self, key: impl ToString, value: impl Into<ParameterValue>) -> Self {
+        let parameters = self.parameters.get_or_insert_with(HashMap::new);
+        let value = value.into();
+        match parameters.entry(key.to_string()) {
+            Entry::Occupied(mut entry) => match entry.get_mut() {
+                ParameterValue::Array(items) => items.push(value),
+                existing => {
+                    let previous = std::mem::replace(existing, ParameterValue::Null);
+                    *existing = ParameterValue::Array(vec![previous, value]);
+                }
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(ParameterValue::Array(vec![value]));
+            }
+        }
+        self
+    }
+
+    /// Add a parameter only if `condition` is true.
+    pub fn parameter_if(
+        self,
+        condition: bool,
+        key: impl ToString,
+        value: impl Into<ParameterValue>,
+    ) -> Self {
+        if condition {
+            self.parameter(key, value)
+        } else {
+            self
+        }
+    }
+
+    /// Add a parameter only if `value` is `Some`.
+    pub fn parameter_some(
+        self,
+        key: impl ToString,
+        value: Option<impl Into<ParameterValue>>,
+    ) -> Self {
+        match value {
+            Some(value) => self.parameter(key, value),
+            None => self,
+        }
+    }
+
+    /// Add multiple parameters at once.
+    pub fn parameters<K, V, I>(mut self, params: I) -> Self
+    where
+        K: Into<String>,
+        V: Into<ParameterValue>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let parameters = self.parameters.get_or_insert_with(HashMap::new);
+        for (key, value) in params {
+            parameters.insert(key.into(), value.into());
+        }
+        self
+    }
+
+    /// Remove all parameters.
+    pub fn clear_parameters(mut self) -> Self {
+        self.parameters = None;
+        self
+    }
+
+    /// Replace all parameters with `params`.
+    pub fn set_parameters(mut self, params: HashMap<String, ParameterValue>) -> Self {
+        self.parameters = Some(params);
+        self
+    }
+
+    /// Add the entries of a [`ParameterValue::Object`] as parameters.
+    ///
+    /// Existing parameters with the same keys are overwritten. Values that
+    /// are not objects are ignored.
+    pub fn with_parameters_object(mut self, object: ParameterValue) -> Self {
+        if let ParameterValue::Object(entries) = object {
+            self.parameters
+                .get_or_insert_with(HashMap::new)
+                .extend(entries);
+        }
+        self
+    }
+
+    /// Truncate parameters to keep rendered responses within a rough size
+    /// budget.
+    ///
+    /// Strings longer than `max_bytes` are cut at the last character
+    /// boundary within the limit and get a `"… (truncated)"` suffix. Byte
+    /// values are cut to their first `max_bytes` bytes, without a marker as
+    /// it could not be told apart from the data. Arrays
+    /// and objects, including the parameters themselves, keep their first
+    /// element and then further elements while the combined rendered size
+    /// stays within `max_bytes`; the remaining elements are dropped and
+    /// replaced by a single `"… (truncated)"` element, or an entry with that
+    /// key in objects. Object entries are considered in key order, so the
+    /// result is deterministic.
+    pub fn truncate_parameters(mut self, max_bytes: usize) -> Self {
+        if let Some(parameters) = &mut self.parameters {
+            truncate_entries(parameters, max_bytes);
+        }
+        self
+    }
+
+    /// Get the runtime parameters of the error, if any.
+    ///
+    /// The [static parameters](ServiceError::with_static_parameters) are not
+    /// included, as they are only merged in when the error is rendered; read
+    /// them with [`ServiceError::static_parameters`].
+    pub fn parameters_ref(&self) -> Option<&HashMap<String, ParameterValue>> {
+        self.parameters.as_ref()
+    }
+
+    /// Get the runtime parameter stored under `key`, if any.
+    ///
+    /// Like [`ServiceError::parameters_ref`] this ignores the static
+    /// parameters.
+    pub fn parameter_get(&self, key: &str) -> Option<&ParameterValue> {
+        self.parameters.as_ref()?.get(key)
+    }
+
+    /// Get the parameters attached with
+    /// [`ServiceError::with_static_parameters`].
+    ///
+    /// A runtime parameter with the same key takes precedence over these
+    /// when the error is rendered.
+    pub const fn static_parameters(&self) -> &'static [(&'static str, &'static str)] {
+        self.static_parameters
+    }
+
+    /// Carry over the parameters and arguments of another error.
+    ///
+    /// Parameters from `other` are added only for keys that are not already
+    /// present, so existing values take precedence. Arguments from `other`
+    /// are appended after the arguments already bound to this error.
+    pub fn merge_context(mut self, other: &ServiceError) -> Self {
+        if let Some(other_parameters) = &other.parameters {
+            let parameters = self.parameters.get_or_insert_with(HashMap::new);
+            for (key, value) in other_parameters {
+                parameters
+                    .entry(key.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+        self.arguments.extend(other.arguments.iter().cloned());
+        self
+    }
+
+    /// Replace the error code, keeping the name and status.
+    pub fn code(mut self, code: u32) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Move the error code into the namespace `prefix`.
+    ///
+    /// The code becomes `prefix * NAMESPACE_SIZE + code`, see
+    /// [`NAMESPACE_SIZE`] and [`namespace_of`]. This lets services reuse
+    /// local codes such as `1001` without colliding with each other.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the code is not below [`NAMESPACE_SIZE`], as it could not
+    /// be decoded back, or if the namespaced code does not fit into a `u32`.
+    /// Use [`ServiceError::try_with_namespace`] for prefixes or codes that
+    /// are not known to be valid.
+    pub fn with_namespace(self, prefix: u32) -> Self {
+        match self.try_with_namespace(prefix) {
+            Ok(error) => error,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Move the error code into the namespace `prefix`, like
+    /// [`ServiceError::with_namespace`], failing instead of panicking if the
+    /// code is not below [`NAMESPACE_SIZE`] or the namespaced code does not
+    /// fit into a `u32`.
+    pub fn try_with_namespace(mut self, prefix: u32) -> Result<Self, InvalidNamespace> {
+        if self.code >= NAMESPACE_SIZE {
+            return Err(InvalidNamespace::CodeOutOfRange(self.code));
+        }
+        self.code = prefix
+            .checked_mul(NAMESPACE_SIZE)
+            .and_then(|base| base.checked_add(self.code))
+            .ok_or(InvalidNamespace::Overflow {
+                prefix,
+                code: self.code,
+            })?;
+        Ok(self)
+    }
+
+    /// Attach a request or trace id to the error.
+    pub fn trace_id(mut self, id: impl Into<String>) -> Self {
+        self.trace_id = Some(id.into());
+        self
+    }
+
+    /// Set a stable, machine-readable identifier for the error, e.g.
+    /// `"user.not_found"`, serialized as `slug`.
+    pub fn slug(mut self, slug: impl Into<Cow<'a, str>>) -> Self {
+        self.slug = Some(slug.into());
+        self
+    }
+
+    /// Attach a longer explanation to show alongside the message.
+    ///
+    /// Bound arguments are substituted into the detail the same way as into
+    /// the message.
+    pub fn with_detail(mut self, detail: impl Into<Cow<'a, str>>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Attach a note for engineers.
+    ///
+    /// The note shows up in [`ServiceError::log_line`], the `tracing` event
+    /// and `Debug` output, but is never written by a [`ResponseBuilder`].
+    pub fn internal_note(mut self, note: impl Into<String>) -> Self {
+        self.internal_note = Some(note.into());
+        self
+    }
+
+    /// Stamp the error with the current time.
+    ///
+    /// The JSON and YAML builders render it as an RFC 3339 `timestamp`. With
+    /// the `auto-timestamp` feature, errors without a timestamp are stamped
+    /// when converted into a response.
+    pub fn with_timestamp(mut self) -> Self {
+        self.timestamp = Some(SystemTime::now());
+        self
+    }
+
+    /// Attach a link to the documentation of this error.
+    pub fn doc_url(mut self, url: impl Into<String>) -> Self {
+        self.doc_url = Some(url.into());
+        self
+    }
+
+    /// Set the severity of the error.
+    pub fn severity(mut self, level: Severity) -> Self {
+        self.severity = level;
+        self
+    }
+
+    /// Set the category of the error.
+    pub fn category(mut self, category: Category) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Set the challenge sent in the `WWW-Authenticate` response header,
+    /// e.g. `Bearer realm="api", error="invalid_token"`.
+    pub fn www_authenticate(mut self, challenge: impl Into<String>) -> Self {
+        self.www_authenticate = Some(challenge.into());
+        self
+    }
+
+    /// Set the `Retry-After` response header.
+    pub fn retry_after(mut self, retry: RetryAfter) -> Self {
+        self.retry_after = Some(retry);
+        self
+    }
+
+    /// Set the `Cache-Control` response header, e.g. `max-age=60`.
+    pub fn cache_control(mut self, value: impl Into<String>) -> Self {
+        self.cache_control = Some(value.into());
+        self
+    }
+
+    /// Send `Cache-Control: no-store` if the error is rendered with a 5xx
+    /// status, so that transient failures are never cached.
+    ///
+    /// For server errors this takes precedence over
+    /// [`ServiceError::cache_control`]; other errors keep the value set
+    /// there, if any.
+    pub fn no_store_for_5xx(mut self) -> Self {
+        self.no_store_for_5xx = true;
+        self
+    }
+
+    /// Set a custom response builder for formatting the response.
+    pub fn with_response_builder(mut self, builder: impl ResponseBuilder + 'static) -> Self {
+        self.response_builder = Some(Box::new(builder));
+        self
+    }
+
+    /// Attach the underlying error that caused this error.
+    ///
+    /// The source is never rendered into responses; it is kept for logging
+    /// and debugging.
+    pub fn with_source(mut self, source: impl std::error::Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Get the underlying error that caused this error, if any.
+    pub fn source(&self) -> Option<&(dyn std::error::Error + Send + Sync + 'static)> {
+        self.source.as_deref()
+    }
+
+    /// Mark parameter keys as sensitive.
+    ///
+    /// The built-in response builders render the values of these keys as
+    /// `"[REDACTED]"`, including when the key appears inside a nested object.
+    /// The values in `parameters` are left untouched, so they remain
+    /// available for logging.
+    pub fn redact(mut self, keys: &[&str]) -> Self {
+        self.redacted_keys
+            .extend(keys.iter().map(|key| key.to_string()));
+        self
+    }
+
+    /// Transform the formatted message with a closure.
+    ///
+    /// `f` receives the message with all arguments substituted. The result
+    /// becomes the new message and the arguments are cleared, since they
+    /// have already been applied. Any detail is formatted before the
+    /// arguments are cleared.
+    pub fn map_message<F: FnOnce(String) -> String>(mut self, f: F) -> Self {
+        self.message = Cow::Owned(f(self.format_message().into_owned()));
+        self.lazy_message = None;
+        self.detail = self.format_detail().map(Cow::Owned);
+        self.arguments.clear();
+        self
+    }
+
+    /// Compute the message with a closure when the error is rendered.
+    ///
+    /// The closure takes precedence over the static `message` and is only
+    /// called when the message is formatted, so expensive messages cost
+    /// nothing for errors that are discarded. Bound arguments are applied to
+    /// its result. Rendering a response calls the closure once.
+    pub fn with_lazy_message(mut self, f: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        self.lazy_message = Some(LazyMessage(Arc::new(f)));
+        self
+    }
+
+    /// Report placeholders in the message that cannot be resolved.
+    ///
+    /// In strict mode, [`ServiceError::try_format_message`] fails on an
+    /// unresolved placeholder, and with the `tracing` feature a warning is
+    /// emitted when such a message is rendered. The rendered message itself
+    /// keeps the placeholder intact either way.
+    pub fn strict_formatting(mut self, strict: bool) -> Self {
+        self.strict_formatting = strict;
+        self
+    }
+
+    /// Always render this error as plain text, ignoring both the builder set
+    /// with [`ServiceError::with_response_builder`] and the global default
+    /// builder.
+    ///
+    /// Useful for endpoints such as health probes that must respond with
+    /// raw text regardless of the application-wide configuration.
+    pub fn use_fallback_format(mut self) -> Self {
+        self.fallback_format = true;
+        self
+    }
+
+    /// Hide the message of server errors from clients.
+    ///
+    /// For 5xx statuses, the built-in response builders render a generic
+    /// message instead of the detailed one and omit the detail; both stay
+    /// available for logging and tracing. Other statuses are unaffected.
+    pub fn client_safe(mut self) -> Self {
+        self.client_safe = true;
+        self
+    }
+
+    /// Whether the HTTP status is a client error (4xx).
+    pub const fn is_client_error(&self) -> bool {
+        matches!(self.http_status, 400..=499)
+    }
+
+    /// Whether the HTTP status is a server error (5xx).
+    pub const fn is_server_error(&self) -> bool {
+        matches!(self.http_status, 500..=599)
+    }
+
+    /// Restrict the parameters shown in responses to an allow-list.
+    ///
+    /// The built-in response builders only render parameters whose key is
+    /// listed; all parameters stay on the error for logging and tracing.
+    /// Without an allow-list every parameter is shown.
+    pub fn public_parameters(mut self, keys: &[&str]) -> Self {
+        self.public_keys
+            .get_or_insert_with(Vec::new)
+            .extend(keys.iter().map(|key| key.to_string()));
+        self
+    }
+
+    /// Get the runtime parameters merged with the static ones, only
+    /// allocating when static parameters are present.
+    fn all_parameters(&self) -> Option<Cow<'_, HashMap<String, ParameterValue>>> {
+        if self.static_parameters.is_empty() {
+            return self.parameters.as_ref().map(Cow::Borrowed);
+        }
+
+        let mut parameters: HashMap<String, ParameterValue> = self
+            .static_parameters
+            .iter()
+            .map(|&(key, value)| (key.to_string(), ParameterValue::from(value)))
+            .collect();
+        if let Some(runtime) = &self.parameters {
+            parameters.extend(runtime.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        Some(Cow::Owned(parameters))
+    }
+
+    /// Get the parameters as they should appear in a response, limited to
+    /// the public keys and with redacted values replaced.
+    fn response_parameters(&self) -> Option<Cow<'_, HashMap<String, ParameterValue>>> {
+        let parameters = self.all_parameters()?;
+        if self.redacted_keys.is_empty() && self.public_keys.is_none() {
+            return Some(parameters);
+        }
+
+        let mut parameters = parameters.into_owned();
+        if let Some(public_keys) = &self.public_keys {
+            parameters.retain(|key, _| public_keys.contains(key));
+            if parameters.is_empty() {
+                return None;
+            }
+        }
+        redact_entries(&mut parameters, &self.redacted_keys);
+        Some(Cow::Owned(parameters))
+    }
+
+    /// Check that the error is well-formed.
+    ///
+    /// Verifies that the HTTP status is a valid error status, that the name
+    /// is not empty, that every positional placeholder in the message has a
+    /// bound argument (and vice versa), and that the code lies within one of
+    /// the ranges set with [`register_code_ranges`], if any. All problems
+    /// found are reported together.
+    pub fn assert_valid(&self) -> Result<(), Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+
+        match StatusCode::from_u16(self.http_status) {
+            Ok(status) if !status.is_client_error() && !status.is_server_error() => {
+                issues.push(ValidationIssue::NonErrorStatus(self.http_status));
+            }
+            Ok(_) => {}
+            Err(_) => issues.push(ValidationIssue::InvalidStatus(self.http_status)),
+        }
+
+        if self.name.trim().is_empty() {
+            issues.push(ValidationIssue::EmptyName);
+        }
+
+        let placeholders = placeholder_indices(&self.message)
+            .max()
+            .map_or(0, |index| index + 1);
+        if placeholders != self.arguments.len() {
+            issues.push(ValidationIssue::ArgumentCountMismatch {
+                placeholders,
+                arguments: self.arguments.len(),
+            });
+        }
+
+        let ranges = CODE_RANGES
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        if let Some(ranges) = ranges
+            && !ranges.is_empty()
+            && !ranges.iter().any(|range| range.contains(&self.code))
+        {
+            issues.push(ValidationIssue::CodeOutOfRange(self.code));
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Convert into a status code and a typed JSON body, for handlers that
+    /// already return [`axum::Json`].
+    ///
+    /// The body matches the output of [`JsonResponseBuilder`]. Like
+    /// [`IntoResponse::into_response`], this logs the error and records
+    /// metrics when those features are enabled, but ignores any configured
+    /// response builder.
+    #[cfg(feature = "json")]
+    #[cfg_attr(not(feature = "auto-timestamp"), allow(unused_mut))]
+    pub fn into_json(mut self) -> (StatusCode, axum::Json<JsonResponseBody<'static>>) {
+        self.resolve_lazy_message();
+        #[cfg(feature = "auto-timestamp")]
+        self.timestamp.get_or_insert_with(SystemTime::now);
+        #[cfg(feature = "tracing")]
+        self.trace();
+        #[cfg(feature = "metrics")]
+        self.record_metrics();
+
+        let status_code =
+            StatusCode::from_u16(self.http_status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (
+            status_code,
+            axum::Json(JsonResponseBody::new(&self).into_owned()),
+        )
+    }
+
+    /// Emit a `tracing` event describing this error.
+    ///
+    /// Code, name, status and severity are recorded as separate fields. The
+    /// parameters are recorded as a `parameters` field with the `Debug` form
+    /// of the map in key order, which keeps the type of every value.
+    #[cfg(feature = "tracing")]
+    fn trace(&self) {
+        let parameters = self.all_parameters();
+        let parameters = parameters.as_ref().map(|params| {
+            tracing::field::debug(params.iter().collect::<std::collections::BTreeMap<_, _>>())
+        });
+
+        macro_rules! event {
+            ($level:expr) => {
+                tracing::event!(
+                    $level,
+                    code = self.code,
+                    name = %self.name,
+                    status = self.http_status,
+                    severity = %self.severity,
+                    parameters = parameters,
+                    internal_note = self.internal_note.as_deref(),
+                    "{}",
+                    self.format_message()
+                )
+            };
+        }
+
+        match self.severity {
+            Severity::Info => event!(tracing::Level::INFO),
+            Severity::Warning => event!(tracing::Level::WARN),
+            Severity::Error | Severity::Critical => event!(tracing::Level::ERROR),
+        }
+    }
+
+    /// Increment the `service_errors_total` counter for this error.
+    #[cfg(feature = "metrics")]
+    fn record_metrics(&self) {
+        metrics::counter!(
+            "service_errors_total",
+            "code" => self.code.to_string(),
+            "name" => self.name.to_string()
+        )
+        .increment(1);
+    }
+
+    /// Format the message with provided arguments.
+    ///
+    /// Messages without arguments or placeholders are returned as-is,
+    /// without allocating.
+    pub fn format_message(&self) -> Cow<'_, str> {
+        self.apply_arguments(self.message_template())
+    }
+
+    /// Substitute the bound arguments into `template`, returning it as-is
+    /// if there is nothing to substitute.
+    fn apply_arguments<'t>(&self, template: Cow<'t, str>) -> Cow<'t, str> {
+        if self.arguments.is_empty() || !template.contains('{') {
+            return template;
+        }
+        Cow::Owned(format_template(&template, &self.arguments))
+    }
+
+    /// Evaluate the lazy message, if set, and store it as the message so
+    /// that rendering calls the closure only once.
+    fn resolve_lazy_message(&mut self) {
+        if let Some(LazyMessage(f)) = self.lazy_message.take() {
+            self.message = Cow::Owned(f());
+        }
+    }
+
+    /// Get the message template, evaluating the lazy message if set.
+    fn message_template(&self) -> Cow<'_, str> {
+        match &self.lazy_message {
+            Some(LazyMessage(f)) => Cow::Owned(f()),
+            None => Cow::Borrowed(&self.message),
+        }
+    }
+
+    /// Format the message, failing in strict mode if a placeholder cannot
+    /// be resolved.
+    ///
+    /// Without [`ServiceError::strict_formatting`] this always succeeds and
+    /// leaves unresolved placeholders intact. Like
+    /// [`ServiceError::format_message`], messages without arguments or
+    /// placeholders are returned without allocating.
+    pub fn try_format_message(&self) -> Result<Cow<'_, str>, UnresolvedPlaceholder> {
+        let template = self.message_template();
+        if self.strict_formatting
+            && let Some(placeholder) = unresolved_placeholder(&template, &self.arguments)
+        {
+            return Err(UnresolvedPlaceholder(placeholder));
+        }
+        Ok(self.apply_arguments(template))
+    }
+
+    /// Format the detail with provided arguments.
+    fn format_detail(&self) -> Option<String> {
+        self.detail
+            .as_ref()
+            .map(|detail| format_template(detail, &self.arguments))
+    }
+
+    /// Get the detail as it should appear in a response, hidden for server
+    /// errors in client-safe mode.
+    fn response_detail(&self) -> Option<String> {
+        if self.client_safe && self.http_status >= 500 {
+            return None;
+        }
+        self.format_detail()
+    }
+
+    /// Get the message as it should appear in a response.
+    fn response_message(&self) -> Cow<'_, str> {
+        if self.client_safe && self.http_status >= 500 {
+            return Cow::Borrowed(CLIENT_SAFE_MESSAGE);
+        }
+        // Strict mode only reports unresolved placeholders here; the
+        // response falls back to lenient formatting of the same template.
+        let template = self.message_template();
+        if self.strict_formatting
+            && let Some(_placeholder) = unresolved_placeholder(&template, &self.arguments)
+        {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                code = self.code,
+                placeholder = %_placeholder,
+                "unresolved placeholder in error message"
+            );
+        }
+        self.apply_arguments(template)
+    }
+
+    /// Render the error as a single structured log line.
+    ///
+    /// The line has the form `code=1001 name=VALIDATION_ERROR status=400
+    /// msg="..."`, followed by the quoted internal note as `note="..."` if
+    /// set, and one `key=value` pair per parameter in key order. The message
+    /// is always quoted; parameter values are quoted when they contain
+    /// whitespace, `=` or `"`. Unlike rendered responses, the full message,
+    /// the internal note and all parameters are included.
+    pub fn log_line(&self) -> String {
+        let mut line = format!(
+            "code={} name={} status={} msg=",
+            self.code, self.name, self.http_status
+        );
+        write_log_quoted(&mut line, &self.format_message());
+        if let Some(note) = &self.internal_note {
+            line.push_str(" note=");
+            write_log_quoted(&mut line, note);
+        }
+
+        if let Some(parameters) = self.all_parameters() {
+            let mut entries: Vec<_> = parameters.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, value) in entries {
+                let value = value.to_string();
+                line.push(' ');
+                line.push_str(key);
+                line.push('=');
+                if value.is_empty()
+                    || value.contains(|c: char| c.is_whitespace() || c == '=' || c == '"')
+                {
+                    write_log_quoted(&mut line, &value);
+                } else {
+                    line.push_str(&value);
+                }
+            }
+        }
+
+        line
+    }
+
+    /// Resolve the message for the given locale using a [`MessageCatalog`].
+    ///
+    /// Falls back to the default `message` when the catalog has no template
+    /// for this error's code and locale. Bound arguments are applied to
+    /// whichever template is selected.
+    pub fn localize(&self, catalog: &MessageCatalog, locale: &str) -> Cow<'_, str> {
+        match catalog.get(self.code, locale) {
+            Some(template) => Cow::Owned(format_template(template, &self.arguments)),
+            None => self.format_message(),
+        }
+    }
+
+    /// Convert into an error that owns all of its data, copying any
+    /// borrowed name, slug, message or detail.
+    ///
+    /// Unlike [`Clone`], this keeps the response builder.
+    pub fn into_owned(self) -> ServiceError<'static> {
+        ServiceError {
+            code: self.code,
+            name: Cow::Owned(self.name.into_owned()),
+            slug: self.slug.map(|slug| Cow::Owned(slug.into_owned())),
+            http_status: self.http_status,
+            message: Cow::Owned(self.message.into_owned()),
+            lazy_message: self.lazy_message,
+            detail: self.detail.map(|detail| Cow::Owned(detail.into_owned())),
+            internal_note: self.internal_note,
+            arguments: self.arguments,
+            parameters: self.parameters,
+            static_parameters: self.static_parameters,
+            trace_id: self.trace_id,
+            doc_url: self.doc_url,
+            severity: self.severity,
+            category: self.category,
+            www_authenticate: self.www_authenticate,
+            retry_after: self.retry_after,
+            cache_control: self.cache_control,
+            timestamp: self.timestamp,
+            response_builder: self.response_builder,
+            redacted_keys: self.redacted_keys,
+            public_keys: self.public_keys,
+            client_safe: self.client_safe,
+            strict_formatting: self.strict_formatting,
+            fallback_format: self.fallback_format,
+            no_store_for_5xx: self.no_store_for_5xx,
+            source: self.source,
+        }
+    }
+}
+
+impl ServiceError<'static> {
+    /// Create a new [`ServiceError`] that owns its name and message, e.g.
+    /// for errors built from runtime data.
+    pub fn owned(code: u32, name: String, status: u16, message: String) -> Self {
+        ServiceError {
+            name: Cow::Owned(name),
+            message: Cow::Owned(message),
+            ..ServiceError::new(code, "", status, "")
+        }
+    }
+
+    /// Create a new [`ServiceError`] from the definition registered under
+    /// `code`, or `None` if the registry has no such definition.
+    pub fn from_registry(registry: &ErrorRegistry, code: u32) -> Option<Self> {
+        registry.get(code)
+    }
+
+    /// Create a new [`ServiceError`] from the definition registered under
+    /// `code` in the global catalog set with [`register_code_catalog`].
+    ///
+    /// Returns `None` if no catalog is registered or it has no such
+    /// definition.
+    pub fn from_code(code: u32) -> Option<Self> {
+        CODE_CATALOG
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()?
+            .get(code)
+    }
+
+    /// Create a new [`ServiceError`] from an HTTP status alone.
+    ///
+    /// The code is the status number, the message is the canonical reason
+    /// phrase (e.g. `"Not Found"`) and the name is that phrase in
+    /// `SCREAMING_SNAKE_CASE` (e.g. `"NOT_FOUND"`), matching the
+    /// [`StatusCode`] constant names. Names registered with
+    /// [`register_status_names`] take precedence.
+    pub fn from_status(status: StatusCode) -> Self {
+        let reason = status.canonical_reason().unwrap_or("Unknown");
+        let registered = STATUS_NAMES
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .and_then(|names| names.get(&status.as_u16()).cloned());
+        let name = registered.unwrap_or_else(|| {
+            reason
+                .replace('\'', "")
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(str::to_ascii_uppercase)
+                .collect::<Vec<_>>()
+                .join("_")
+        });
+
+        let mut error = ServiceError::new(status.as_u16() as u32, "", status.as_u16(), reason);
+        error.name = Cow::Owned(name);
+        error
+    }
+
+    /// Create a `429 Too Many Requests` error telling the client when to
+    /// retry in the `Retry-After` header.
+    ///
+    /// The code, name and message are derived from the status as in
+    /// [`ServiceError::from_status`].
+    pub fn rate_limited(retry: RetryAfter) -> Self {
+        Self::from_status(StatusCode::TOO_MANY_REQUESTS).retry_after(retry)
+    }
+}
+
+impl<'a> From<(u16, &'a str)> for ServiceError<'a> {
+    /// Create a quick error from an HTTP status and a message, e.g.
+    /// `(404, "User not found").into()`.
+    ///
+    /// The code is the status number and the name is derived from the
+    /// status as in [`ServiceError::from_status`] (e.g. `"NOT_FOUND"`), or
+    /// `"UNKNOWN"` for a number that is not a valid status.
+    fn from((status, message): (u16, &'a str)) -> Self {
+        let name = StatusCode::from_u16(status)
+            .map(|status| ServiceError::from_status(status).name)
+            .unwrap_or(Cow::Borrowed("UNKNOWN"));
+
+        ServiceError {
+            name,
+            ..ServiceError::new(status as u32, "", status, message)
+        }
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for ServiceError<'static> {
+    /// Convert an [`anyhow::Error`] into a generic internal server error.
+    ///
+    /// The message is the outermost context of the error; the full chain is
+    /// kept as the source.
+    fn from(error: anyhow::Error) -> Self {
+        ServiceError {
+            message: Cow::Owned(error.to_string()),
+            source: Some(Arc::from(Box::<dyn std::error::Error + Send + Sync>::from(
+                error,
+            ))),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "validator")]
+impl From<validator::ValidationErrors> for ServiceError<'static> {
+    /// Convert [`validator::ValidationErrors`] into an unprocessable entity
+    /// error.
+    ///
+    /// The `fields` parameter maps each invalid field to an array of its
+    /// failures, each an object with a `code` and, if set, a `message`.
+    fn from(errors: validator::ValidationErrors) -> Self {
+        let fields = errors
+            .field_errors()
+            .into_iter()
+            .map(|(field, failures)| {
+                let failures = failures
+                    .iter()
+                    .map(|failure| {
+                        let mut entry = HashMap::new();
+                        entry.insert(
+                            "code".to_string(),
+                            ParameterValue::from(failure.code.to_string()),
+                        );
+                        if let Some(message) = &failure.message {
+                            entry.insert(
+                                "message".to_string(),
+                                ParameterValue::from(message.to_string()),
+                            );
+                        }
+                        ParameterValue::Object(entry)
+                    })
+                    .collect();
+                (field.to_string(), ParameterValue::Array(failures))
+            })
+            .collect();
+
+        ServiceError::new(
+            4220,
+            "VALIDATION_FAILED",
+            422,
+            "The request contains invalid fields",
+        )
+        .parameter("fields", ParameterValue::Object(fields))
+    }
+}
+
+impl From<ServiceError<'_>> for ParameterValue {
+    /// Convert an error into an object with its `code`, `name`, rendered
+    /// `message` and, if present, its `parameters`, so that it can be nested
+    /// as a parameter of another error.
+    ///
+    /// The message and parameters are rendered as in a response, so
+    /// redacted and non-public parameters stay hidden.
+    fn from(error: ServiceError<'_>) -> Self {
+        let mut entries = HashMap::new();
+        entries.insert("code".to_string(), ParameterValue::from(error.code));
+        entries.insert(
+            "name".to_string(),
+            ParameterValue::from(error.name.as_ref()),
+        );
+        entries.insert(
+            "message".to_string(),
+            ParameterValue::from(error.response_message().into_owned()),
+        );
+        if let Some(parameters) = error.response_parameters() {
+            entries.insert(
+                "parameters".to_string(),
+                ParameterValue::Object(parameters.into_owned()),
+            );
+        }
+        ParameterValue::Object(entries)
+    }
+}
+
+/// Format a point in time as an RFC 3339 UTC timestamp with millisecond
+/// precision, e.g. `2024-05-01T12:30:45.123Z`.
+///
+/// Times before the Unix epoch are clamped to the epoch.
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn format_system_time(time: SystemTime) -> String {
+    let since_epoch = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Write a point in time as an RFC 7231 IMF-fixdate, e.g.
+/// `Sun, 06 Nov 1994 08:49:37 GMT`.
+///
+/// Times before the Unix epoch are clamped to the epoch.
+fn write_http_date(f: &mut Formatter<'_>, time: SystemTime) -> std::fmt::Result {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let secs = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    write!(
+        f,
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        // The epoch was a Thursday
+        WEEKDAYS[(days % 7) as usize],
+        day,
+        MONTHS[month as usize - 1],
+        year,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Convert days since the Unix epoch into a `(year, month, day)` civil
+/// date, after Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: u64) -> (i64, i64, i64) {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Append a double-quoted log value, escaping quotes, backslashes and line
+/// breaks.
+fn write_log_quoted(line: &mut String, value: &str) {
+    line.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => line.push_str("\\\""),
+            '\\' => line.push_str("\\\\"),
+            '\n' => line.push_str("\\n"),
+            '\r' => line.push_str("\\r"),
+            c => line.push(c),
+        }
+    }
+    line.push('"');
+}
+
+/// A closure producing an error message, see
+/// [`ServiceError::with_lazy_message`].
+#[derive(Clone)]
+struct LazyMessage(Arc<dyn Fn() -> String + Send + Sync>);
+
+impl std::fmt::Debug for LazyMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LazyMessage(..)")
+    }
+}
+
+/// Message rendered in place of server error messages by
+/// [`ServiceError::client_safe`].
+const CLIENT_SAFE_MESSAGE: &str = "An internal error occurred";
+
+/// Marker appended where [`ServiceError::truncate_parameters`] cuts a value.
+const TRUNCATED: &str = "… (truncated)";
+
+/// Truncate a parameter value, see [`ServiceError::truncate_parameters`].
+fn truncate_value(value: &mut ParameterValue, max_bytes: usize) {
+    match value {
+        ParameterValue::String(s) if s.len() > max_bytes => {
+            let mut end = max_bytes;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            s.truncate(end);
+            s.push_str(TRUNCATED);
+        }
+        ParameterValue::Bytes(bytes) => bytes.truncate(max_bytes),
+        ParameterValue::Array(items) => {
+            let mut size = 0;
+            let mut keep = 0;
+            for item in items.iter_mut() {
+                truncate_value(item, max_bytes);
+                size += item.to_string().len();
+                if size > max_bytes && keep > 0 {
+                    break;
+                }
+                keep += 1;
+            }
+            if keep < items.len() {
+                items.truncate(keep);
+                items.push(ParameterValue::from(TRUNCATED));
+            }
+        }
+        ParameterValue::Object(entries) => truncate_entries(entries, max_bytes),
+        _ => {}
+    }
+}
+
+/// Truncate the entries of an object, see
+/// [`ServiceError::truncate_parameters`].
+fn truncate_entries(entries: &mut HashMap<String, ParameterValue>, max_bytes: usize) {
+    let mut keys: Vec<String> = entries.keys().cloned().collect();
+    keys.sort();
+
+    let mut size = 0;
+    let mut dropped = false;
+    for (i, key) in keys.into_iter().enumerate() {
+        if !dropped && let Some(value) = entries.get_mut(&key) {
+            truncate_value(value, max_bytes);
+            size += key.len() + value.to_string().len();
+            dropped = size > max_bytes && i > 0;
+        }
+        if dropped {
+            entries.remove(&key);
+        }
+    }
+    if dropped {
+        entries.insert(TRUNCATED.to_string(), ParameterValue::Null);
+    }
+}
+
+/// Placeholder rendered in place of redacted parameter values.
+const REDACTED: &str = "[REDACTED]";
+
+/// Replace the values of redacted keys in a parameter map, recursing into
+/// nested objects and arrays.
+fn redact_entries(entries: &mut HashMap<String, ParameterValue>, keys: &[String]) {
+    for (key, value) in entries.iter_mut() {
+        if keys.contains(key) {
+            *value = ParameterValue::String(REDACTED.to_string());
+        } else {
+            redact_value(value, keys);
+        }
+    }
+}
+
+fn redact_value(value: &mut ParameterValue, keys: &[String]) {
+    match value {
+        ParameterValue::Object(entries) => redact_entries(entries, keys),
+        ParameterValue::Array(items) => {
+            for item in items {
+                redact_value(item, keys);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Iterate over the indices of all positional `{n}` placeholders in a template.
+fn placeholder_indices(template: &str) -> impl Iterator<Item = usize> + '_ {
+    template.split('{').skip(1).filter_map(|segment| {
+        let (placeholder, _) = segment.split_once('}')?;
+        Placeholder::parse(placeholder).map(|placeholder| placeholder.index)
+    })
+}
+
+/// Find the first placeholder-like token in a template that the arguments
+/// cannot resolve.
+///
+/// Besides positional placeholders, identifier tokens such as `{name}` are
+/// treated as placeholders, so that templates written for named parameters
+/// are reported rather than silently rendered verbatim.
+fn unresolved_placeholder(template: &str, arguments: &[ParameterValue]) -> Option<String> {
+    template.split('{').skip(1).find_map(|segment| {
+        let (placeholder, _) = segment.split_once('}')?;
+        let resolved = match Placeholder::parse(placeholder) {
+            Some(placeholder) => placeholder.index < arguments.len(),
+            None => {
+                let name = placeholder.split(':').next().unwrap_or_default();
+                name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            }
+        };
+        (!resolved).then(|| format!("{{{}}}", placeholder))
+    })
+}
+
+/// Replace positional `{n}` placeholders in a template with the given arguments.
+///
+/// Placeholders may carry a format spec after a colon, such as `{0:05}` or
+/// `{1:.2}`, which is applied to numeric arguments. Placeholders that cannot
+/// be resolved are left intact.
+fn format_template(template: &str, arguments: &[ParameterValue]) -> String {
+    let mut formatted = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        formatted.push_str(&rest[..start]);
+        let candidate = &rest[start..];
+        let resolved = candidate.find('}').and_then(|end| {
+            let placeholder = Placeholder::parse(&candidate[1..end])?;
+            let argument = arguments.get(placeholder.index)?;
+            Some((end, placeholder.apply(argument)))
+        });
+        match resolved {
+            Some((end, value)) => {
+                formatted.push_str(&value);
+                rest = &candidate[end + 1..];
+            }
+            None => {
+                formatted.push('{');
+                rest = &candidate[1..];
+            }
+        }
+    }
+    formatted.push_str(rest);
+    formatted
+}
+
+/// A positional placeholder with an optional format spec.
+///
+/// The spec grammar is `[0][width][.precision]`, where a leading `0` pads
+/// numbers with zeros instead of spaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placeholder {
+    index: usize,
+    zero_pad: bool,
+    width: Option<usize>,
+    precision: Option<usize>,
+}
+
+impl Placeholder {
+    /// Parse the contents of a placeholder, without the surrounding braces.
+    fn parse(placeholder: &str) -> Option<Self> {
+        let (index, spec) = match placeholder.split_once(':') {
+            Some((index, spec)) => (index, spec),
+            None => (placeholder, ""),
+        };
+        if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let (width, precision) = match spec.split_once('.') {
+            Some((width, precision)) => (width, Some(precision)),
+            None => (spec, None),
+        };
+        let zero_pad = width.len() > 1 && width.starts_with('0');
+        let width = if zero_pad { &width[1..] } else { width };
+
+        Some(Self {
+            index: index.parse().ok()?,
+            zero_pad,
+            width: match width {
+                "" => None,
+                width => Some(width.parse().ok()?),
+            },
+            precision: match precision {
+                Some(precision) => Some(precision.parse().ok()?),
+                None => None,
+            },
+        })
+    }
+
+    /// Render an argument according to the format spec.
+    ///
+    /// The spec only applies to numeric arguments; anything else is rendered
+    /// unchanged.
+    fn apply(&self, argument: &ParameterValue) -> String {
+        let width = self.width.unwrap_or(0);
+
+        let number = match argument {
+            ParameterValue::Integer(number) if self.precision.is_none() => {
+                return if self.zero_pad {
+                    format!("{:0width$}", number)
+                } else {
+                    format!("{:width$}", number)
+                };
+            }
+            ParameterValue::Integer(number) => *number as f64,
+            ParameterValue::Float(number) => *number,
+            _ => return argument.to_string(),
+        };
+
+        match (self.zero_pad, self.precision) {
+            (true, Some(precision)) => format!("{:0width$.precision$}", number),
+            (true, None) => format!("{:0width$}", number),
+            (false, Some(precision)) => format!("{:width$.precision$}", number),
+            (false, None) => format!("{:width$}", number),
+        }
+    }
+}
+
+/// A catalog of localized message templates keyed by error code and locale.
+///
+/// Locales are matched case-insensitively. A lookup for a regional locale
+/// such as `de-AT` falls back to its primary language (`de`) when no exact
+/// entry exists.
+#[derive(Debug, Clone, Default)]
+pub struct MessageCatalog {
+    messages: HashMap<(u32, String), String>,
+}
+
+impl MessageCatalog {
+    pub fn new() -> Self {
+        Self {
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Add a localized template for the given error code and locale.
+    pub fn message(
+        mut self,
+        code: u32,
+        locale: impl AsRef<str>,
+        template: impl Into<String>,
+    ) -> Self {
+        self.insert(code, locale, template);
+        self
+    }
+
+    /// Add a localized template for the given error code and locale.
+    pub fn insert(&mut self, code: u32, locale: impl AsRef<str>, template: impl Into<String>) {
+        self.messages
+            .insert((code, locale.as_ref().to_lowercase()), template.into());
+    }
+
+    /// Look up the template for the given error code and locale.
+    pub fn get(&self, code: u32, locale: &str) -> Option<&str> {
+        let locale = locale.to_lowercase();
+        self.messages
+            .get(&(code, locale.clone()))
+            .or_else(|| {
+                let (language, _) = locale.split_once(['-', '_'])?;
+                self.messages.get(&(code, language.to_string()))
+            })
+            .map(String::as_str)
+    }
+}
+
+impl<'a> ServiceError<'a> {
+    /// Convert into a response like [`IntoResponse::into_response`], but
+    /// render the body with [`ResponseBuilder::build_with_parts`] so that
+    /// the builder can use request-scoped data such as headers or
+    /// extensions.
+    pub fn into_response_with_parts(self, parts: &Parts) -> Response {
+        self.render(Some(parts))
+    }
+
+    #[cfg_attr(not(feature = "auto-timestamp"), allow(unused_mut))]
+    fn render(mut self, parts: Option<&Parts>) -> Response {
+        self.resolve_lazy_message();
+        #[cfg(feature = "auto-timestamp")]
+        self.timestamp.get_or_insert_with(SystemTime::now);
+        #[cfg(feature = "tracing")]
+        self.trace();
+        #[cfg(feature = "metrics")]
+        self.record_metrics();
+
         let status_code =
             StatusCode::from_u16(self.http_status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
 
-        let (body, content_type) = if let Some(builder) = &self.response_builder {
+        let build = |builder: &dyn ResponseBuilder| match parts {
+            Some(parts) => builder.build_with_parts(&self, parts),
+            None => builder.build(&self),
+        };
+
+        let (body, content_type) = if self.fallback_format {
+            // Bypass all configured builders
+            PlainTextResponseBuilder::new().build(&self)
+        } else if let Some(builder) = &self.response_builder {
             // Use instance-specific builder
-            builder.build(&self)
+            build(builder.as_ref())
         } else if let Some(default_builder) = get_default_response_builder() {
             // Use global default builder
-            default_builder.build(&self)
+            build(default_builder.as_ref())
         } else {
-            // Fallback to plain text format
-            let text = if let Some(ref params) = self.parameters {
-                let param_display: Vec<String> = params
-                    .iter()
-                    .map(|(k, v)| format!("{}: {}", k, v))
-                    .collect();
-                format!(
-                    "Error {}: {} - {} (Parameters: {{{}}})",
-                    self.code,
-                    self.name,
-                    self.format_message(),
-                    param_display.join(", ")
-                )
-            } else {
-                format!(
-                    "Error {}: {} - {}",
-                    self.code,
-                    self.name,
-                    self.format_message()
-                )
-            };
-            (text, "text/plain")
+            // Fallback to the built-in default format
+            get_default_response_format().build(&self)
+        };
+
+        let mut response = (status_code, [("content-type", content_type)], body).into_response();
+
+        if let Some(challenge) = self
+            .www_authenticate
+            .and_then(|challenge| HeaderValue::from_str(&challenge).ok())
+        {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, challenge);
+        }
+
+        if let Some(retry) = self
+            .retry_after
+            .and_then(|retry| HeaderValue::from_str(&retry.to_string()).ok())
+        {
+            response.headers_mut().insert(header::RETRY_AFTER, retry);
+        }
+
+        let cache_control = if self.no_store_for_5xx && status_code.is_server_error() {
+            Some("no-store".to_string())
+        } else {
+            self.cache_control
+        };
+        if let Some(value) = cache_control.and_then(|value| HeaderValue::from_str(&value).ok()) {
+            response.headers_mut().insert(header::CACHE_CONTROL, value);
+        }
+
+        response
+    }
+}
+
+impl<'a> IntoResponse for ServiceError<'a> {
+    fn into_response(self) -> Response {
+        self.render(None)
+    }
+}
+
+/// A simple JSON response builder that serializes the ServiceError as JSON.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone)]
+pub struct JsonResponseBuilder {
+    style: SerializeStyle,
+    field_names: HashMap<String, String>,
+    charset: Option<Charset>,
+    pretty: bool,
+}
+
+#[cfg(feature = "json")]
+impl Default for JsonResponseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "json")]
+impl JsonResponseBuilder {
+    pub fn new() -> Self {
+        Self {
+            style: SerializeStyle::default(),
+            field_names: HashMap::new(),
+            charset: Some(Charset::Utf8),
+            pretty: false,
+        }
+    }
+
+    /// Indent the output for readability, e.g. in development. Off by
+    /// default to keep payloads compact.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Set the `charset` parameter of the content-type, or send a bare
+    /// `application/json` with `None`. Defaults to [`Charset::Utf8`].
+    ///
+    /// This only changes the header; the body is always UTF-8.
+    pub fn with_charset(mut self, charset: Option<Charset>) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Serialize parameter keys in lexicographic order, at every nesting
+    /// level, for stable output. Off by default.
+    pub fn sorted_keys(mut self, sorted: bool) -> Self {
+        self.style.sorted_keys = sorted;
+        self
+    }
+
+    /// Serialize integer and float parameters as JSON strings, at every
+    /// nesting level, e.g. `"9007199254740993"`. Off by default.
+    ///
+    /// This keeps values exact for clients such as JavaScript that parse
+    /// JSON numbers as doubles. Non-finite floats are still `null`.
+    pub fn with_lossless_numbers(mut self, lossless: bool) -> Self {
+        self.style.lossless_numbers = lossless;
+        self
+    }
+
+    /// Set how binary parameters are serialized, as a JSON string at every
+    /// nesting level. Defaults to [`BytesEncoding::Base64`].
+    pub fn with_bytes_encoding(mut self, encoding: BytesEncoding) -> Self {
+        self.style.bytes = encoding;
+        self
+    }
+
+    /// Serialize the field `logical` under the key `wire`, e.g.
+    /// `rename_field("code", "errorCode")`.
+    ///
+    /// Fields are identified by their default names: `code`, `name`,
+    /// `slug`, `message`, `detail`, `parameters`, `trace_id`, `type`,
+    /// `severity`, `category` and `timestamp`.
+    pub fn rename_field(mut self, logical: impl Into<String>, wire: impl Into<String>) -> Self {
+        self.field_names.insert(logical.into(), wire.into());
+        self
+    }
+}
+
+#[cfg(feature = "json")]
+impl ResponseBuilder for JsonResponseBuilder {
+    fn content_type(&self) -> &'static str {
+        MediaType::Json.content_type(self.charset)
+    }
+
+    fn build(&self, error: &ServiceError) -> (String, &'static str) {
+        let mut json = String::new();
+        if self.write_to(error, &mut json).is_err() {
+            json = format!("{{\"error\":\"Failed to serialize error {}\"}}", error.code);
+        }
+
+        (json, self.content_type())
+    }
+
+    fn write_to(
+        &self,
+        error: &ServiceError,
+        w: &mut dyn std::fmt::Write,
+    ) -> Result<&'static str, std::fmt::Error> {
+        let body = JsonResponseBody::new(error).with_style(self.style);
+        let result = if self.pretty {
+            let mut serializer = serde_json::Serializer::pretty(FmtWriter(w));
+            body.serialize_with_names(&mut serializer, &self.field_names)
+        } else {
+            let mut serializer = serde_json::Serializer::new(FmtWriter(w));
+            body.serialize_with_names(&mut serializer, &self.field_names)
+        };
+        result.map_err(|_| std::fmt::Error)?;
+        Ok(self.content_type())
+    }
+}
+
+/// Adapts a [`std::fmt::Write`] for serializers that write to an
+/// [`std::io::Write`].
+///
+/// `serde_json` only splits its output on character boundaries, so every
+/// chunk is valid UTF-8.
+#[cfg(feature = "json")]
+struct FmtWriter<'a>(&'a mut dyn std::fmt::Write);
+
+#[cfg(feature = "json")]
+impl std::io::Write for FmtWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let chunk = std::str::from_utf8(buf)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        self.0.write_str(chunk).map_err(std::io::Error::other)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The serialized body of an error, as rendered by [`JsonResponseBuilder`]
+/// and returned by [`ServiceError::into_json`].
+///
+/// The message and detail are already formatted, and redacted or
+/// non-public parameters are already masked.
+#[cfg(any(feature = "json", feature = "yaml"))]
+#[derive(Debug, Clone)]
+pub struct JsonResponseBody<'a> {
+    pub code: u32,
+    pub name: Cow<'a, str>,
+    pub slug: Option<Cow<'a, str>>,
+    pub message: Cow<'a, str>,
+    pub detail: Option<String>,
+    parameters: Option<ResponseParameters<'a>>,
+    pub trace_id: Option<String>,
+    /// Serialized as `type`
+    pub doc_url: Option<String>,
+    pub severity: Severity,
+    pub category: Option<Category>,
+    pub timestamp: Option<String>,
+}
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+impl Serialize for JsonResponseBody<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_with_names(serializer, &HashMap::new())
+    }
+}
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+impl<'a> JsonResponseBody<'a> {
+    /// Render the body of `error`, borrowing its name and parameters
+    /// unless they have to be redacted.
+    fn new(error: &'a ServiceError<'_>) -> Self {
+        Self {
+            code: error.code,
+            name: Cow::Borrowed(&error.name),
+            slug: error.slug.as_deref().map(Cow::Borrowed),
+            message: error.response_message(),
+            detail: error.response_detail(),
+            parameters: error
+                .response_parameters()
+                .map(|entries| ResponseParameters {
+                    entries,
+                    style: SerializeStyle::default(),
+                }),
+            trace_id: error.trace_id.clone(),
+            doc_url: error.doc_url.clone(),
+            severity: error.severity,
+            category: error.category,
+            timestamp: error.timestamp.map(format_system_time),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn with_style(mut self, style: SerializeStyle) -> Self {
+        if let Some(parameters) = &mut self.parameters {
+            parameters.style = style;
+        }
+        self
+    }
+
+    /// Serialize the body as a map, renaming the fields listed in `names`.
+    ///
+    /// Fields are identified by their default serialized name, e.g. `type`
+    /// for the documentation link. Unset optional fields are skipped.
+    fn serialize_with_names<S: serde::Serializer>(
+        &self,
+        serializer: S,
+        names: &HashMap<String, String>,
+    ) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let name = |field: &'static str| names.get(field).map_or(field, String::as_str);
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry(name("code"), &self.code)?;
+        map.serialize_entry(name("name"), &self.name)?;
+        if let Some(slug) = &self.slug {
+            map.serialize_entry(name("slug"), slug)?;
+        }
+        map.serialize_entry(name("message"), &self.message)?;
+        if let Some(detail) = &self.detail {
+            map.serialize_entry(name("detail"), detail)?;
+        }
+        if let Some(parameters) = &self.parameters {
+            map.serialize_entry(name("parameters"), parameters)?;
+        }
+        if let Some(trace_id) = &self.trace_id {
+            map.serialize_entry(name("trace_id"), trace_id)?;
+        }
+        if let Some(doc_url) = &self.doc_url {
+            map.serialize_entry(name("type"), doc_url)?;
+        }
+        map.serialize_entry(name("severity"), &self.severity)?;
+        if let Some(category) = &self.category {
+            map.serialize_entry(name("category"), category)?;
+        }
+        if let Some(timestamp) = &self.timestamp {
+            map.serialize_entry(name("timestamp"), timestamp)?;
+        }
+        map.end()
+    }
+
+    /// The response parameters, if any.
+    pub fn parameters(&self) -> Option<&HashMap<String, ParameterValue>> {
+        self.parameters
+            .as_ref()
+            .map(|parameters| parameters.entries.as_ref())
+    }
+
+    /// Convert into a body that owns all of its data.
+    pub fn into_owned(self) -> JsonResponseBody<'static> {
+        JsonResponseBody {
+            code: self.code,
+            name: Cow::Owned(self.name.into_owned()),
+            slug: self.slug.map(|slug| Cow::Owned(slug.into_owned())),
+            message: Cow::Owned(self.message.into_owned()),
+            detail: self.detail,
+            parameters: self.parameters.map(|parameters| ResponseParameters {
+                entries: Cow::Owned(parameters.entries.into_owned()),
+                style: parameters.style,
+            }),
+            trace_id: self.trace_id,
+            doc_url: self.doc_url,
+            severity: self.severity,
+            category: self.category,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Options for the serialized form of parameters.
+#[cfg(any(feature = "json", feature = "yaml"))]
+#[derive(Debug, Clone, Copy, Default)]
+struct SerializeStyle {
+    /// Sort object keys lexicographically at every level.
+    sorted_keys: bool,
+    /// Serialize integers and finite floats as strings.
+    lossless_numbers: bool,
+    /// Encoding of binary values.
+    bytes: BytesEncoding,
+}
+
+/// The parameters of a [`JsonResponseBody`], serialized in a given style.
+#[cfg(any(feature = "json", feature = "yaml"))]
+#[derive(Debug, Clone)]
+struct ResponseParameters<'a> {
+    entries: Cow<'a, HashMap<String, ParameterValue>>,
+    style: SerializeStyle,
+}
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+impl Serialize for ResponseParameters<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_entries(serializer, &self.entries, self.style)
+    }
+}
+
+/// Serializes a [`ParameterValue`] in a given style, at every nesting
+/// level.
+#[cfg(any(feature = "json", feature = "yaml"))]
+struct StyledValue<'a> {
+    value: &'a ParameterValue,
+    style: SerializeStyle,
+}
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+impl Serialize for StyledValue<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let style = self.style;
+        match self.value {
+            ParameterValue::Integer(i) if style.lossless_numbers => serializer.collect_str(i),
+            ParameterValue::Bytes(bytes) => serializer.serialize_str(&style.bytes.encode(bytes)),
+            ParameterValue::Float(float) if style.lossless_numbers && float.is_finite() => {
+                serializer.collect_str(float)
+            }
+            ParameterValue::Array(items) => {
+                serializer.collect_seq(items.iter().map(|value| StyledValue { value, style }))
+            }
+            ParameterValue::Object(entries) => serialize_entries(serializer, entries, style),
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+/// Serialize object entries in the given style.
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn serialize_entries<S: serde::Serializer>(
+    serializer: S,
+    entries: &HashMap<String, ParameterValue>,
+    style: SerializeStyle,
+) -> Result<S::Ok, S::Error> {
+    if !style.sorted_keys && !style.lossless_numbers && style.bytes == BytesEncoding::Base64 {
+        return entries.serialize(serializer);
+    }
+    serializer.collect_map(
+        object_entries(entries, style.sorted_keys)
+            .into_iter()
+            .map(|(key, value)| (key, StyledValue { value, style })),
+    )
+}
+
+/// A JSON response builder that wraps the error in a standard envelope:
+/// `{"success": false, "error": {...}}`.
+///
+/// The `error` member contains the same fields as [`JsonResponseBuilder`].
+#[cfg(feature = "json")]
+#[derive(Debug, Clone)]
+pub struct EnvelopeResponseBuilder {
+    success_key: String,
+    error_key: String,
+    charset: Option<Charset>,
+}
+
+#[cfg(feature = "json")]
+impl Default for EnvelopeResponseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "json")]
+impl EnvelopeResponseBuilder {
+    pub fn new() -> Self {
+        Self {
+            success_key: "success".to_string(),
+            error_key: "error".to_string(),
+            charset: Some(Charset::Utf8),
+        }
+    }
+
+    /// Set the `charset` parameter of the content-type, or send a bare
+    /// `application/json` with `None`. Defaults to [`Charset::Utf8`].
+    ///
+    /// This only changes the header; the body is always UTF-8.
+    pub fn with_charset(mut self, charset: Option<Charset>) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Rename the `success` key of the envelope.
+    pub fn with_success_key(mut self, key: impl Into<String>) -> Self {
+        self.success_key = key.into();
+        self
+    }
+
+    /// Rename the `error` key of the envelope.
+    pub fn with_error_key(mut self, key: impl Into<String>) -> Self {
+        self.error_key = key.into();
+        self
+    }
+}
+
+#[cfg(feature = "json")]
+impl ResponseBuilder for EnvelopeResponseBuilder {
+    fn content_type(&self) -> &'static str {
+        MediaType::Json.content_type(self.charset)
+    }
+
+    fn build(&self, error: &ServiceError) -> (String, &'static str) {
+        use serde::ser::SerializeMap;
+
+        struct Envelope<'a> {
+            builder: &'a EnvelopeResponseBuilder,
+            body: JsonResponseBody<'a>,
+        }
+
+        impl Serialize for Envelope<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry(&self.builder.success_key, &false)?;
+                map.serialize_entry(&self.builder.error_key, &self.body)?;
+                map.end()
+            }
+        }
+
+        let envelope = Envelope {
+            builder: self,
+            body: JsonResponseBody::new(error),
+        };
+
+        let json = serde_json::to_string(&envelope).unwrap_or_else(|_| {
+            format!(
+                "{{\"{}\":false,\"{}\":\"Failed to serialize error {}\"}}",
+                self.success_key, self.error_key, error.code
+            )
+        });
+
+        (json, self.content_type())
+    }
+}
+
+/// A JSON response builder for non-production environments that adds the
+/// chain of underlying errors as a `causes` array.
+///
+/// The causes are collected by walking [`std::error::Error::source`] from
+/// the source attached with [`ServiceError::with_source`]. They may contain
+/// internal details, so this builder is never used unless set explicitly.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone)]
+pub struct DebugResponseBuilder {
+    charset: Option<Charset>,
+}
+
+#[cfg(feature = "json")]
+impl Default for DebugResponseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "json")]
+impl DebugResponseBuilder {
+    pub fn new() -> Self {
+        Self {
+            charset: Some(Charset::Utf8),
+        }
+    }
+
+    /// Set the `charset` parameter of the content-type, or send a bare
+    /// `application/json` with `None`. Defaults to [`Charset::Utf8`].
+    ///
+    /// This only changes the header; the body is always UTF-8.
+    pub fn with_charset(mut self, charset: Option<Charset>) -> Self {
+        self.charset = charset;
+        self
+    }
+}
+
+#[cfg(feature = "json")]
+impl ResponseBuilder for DebugResponseBuilder {
+    fn content_type(&self) -> &'static str {
+        MediaType::Json.content_type(self.charset)
+    }
+
+    fn build(&self, error: &ServiceError) -> (String, &'static str) {
+        #[derive(Serialize)]
+        struct DebugResponseBody<'a> {
+            #[serde(flatten)]
+            body: JsonResponseBody<'a>,
+            causes: Vec<String>,
+        }
+
+        let mut causes = Vec::new();
+        let mut source = error
+            .source()
+            .map(|source| source as &dyn std::error::Error);
+        while let Some(cause) = source {
+            causes.push(cause.to_string());
+            source = cause.source();
+        }
+
+        let response_body = DebugResponseBody {
+            body: JsonResponseBody::new(error),
+            causes,
         };
 
-        (status_code, [("content-type", content_type)], body).into_response()
+        let json = serde_json::to_string(&response_body).unwrap_or_else(|_| {
+            format!("{{\"error\":\"Failed to serialize error {}\"}}", error.code)
+        });
+
+        (json, self.content_type())
+    }
+}
+
+/// A YAML response builder that serializes the same fields as
+/// [`JsonResponseBuilder`].
+#[cfg(feature = "yaml")]
+#[derive(Debug, Clone)]
+pub struct YamlResponseBuilder {
+    charset: Option<Charset>,
+}
+
+#[cfg(feature = "yaml")]
+impl Default for YamlResponseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl YamlResponseBuilder {
+    pub fn new() -> Self {
+        Self {
+            charset: Some(Charset::Utf8),
+        }
+    }
+
+    /// Set the `charset` parameter of the content-type, or send a bare
+    /// `application/yaml` with `None`. Defaults to [`Charset::Utf8`].
+    ///
+    /// This only changes the header; the body is always UTF-8.
+    pub fn with_charset(mut self, charset: Option<Charset>) -> Self {
+        self.charset = charset;
+        self
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl ResponseBuilder for YamlResponseBuilder {
+    fn content_type(&self) -> &'static str {
+        MediaType::Yaml.content_type(self.charset)
+    }
+
+    fn build(&self, error: &ServiceError) -> (String, &'static str) {
+        let response_body = JsonResponseBody::new(error);
+
+        let yaml = serde_yaml::to_string(&response_body)
+            .unwrap_or_else(|_| format!("error: Failed to serialize error {}\n", error.code));
+
+        (yaml, self.content_type())
     }
 }
 
-/// A simple JSON response builder that serializes the ServiceError as JSON.
-#[cfg(feature = "json")]
+/// A simple plain text response builder.
+///
+/// By default errors render as `Error {code}: {name} - {message}`, followed
+/// by the parameters, documentation link and trace id when present. A
+/// custom layout can be set with [`PlainTextResponseBuilder::with_template`].
 #[derive(Debug, Clone)]
-pub struct JsonResponseBuilder;
+pub struct PlainTextResponseBuilder {
+    template: Option<String>,
+    style: PlainStyle,
+    charset: Option<Charset>,
+}
 
-#[cfg(feature = "json")]
-impl JsonResponseBuilder {
+impl Default for PlainTextResponseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlainTextResponseBuilder {
     pub fn new() -> Self {
-        Self
+        Self {
+            template: None,
+            style: PlainStyle {
+                bytes: BytesEncoding::Hex,
+                ..PlainStyle::default()
+            },
+            charset: Some(Charset::Utf8),
+        }
+    }
+
+    /// Set the `charset` parameter of the content-type, or send a bare
+    /// `text/plain` with `None`. Defaults to [`Charset::Utf8`].
+    ///
+    /// This only changes the header; the body is always UTF-8.
+    pub fn with_charset(mut self, charset: Option<Charset>) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Render parameter keys in lexicographic order, at every nesting
+    /// level, for stable output. Off by default.
+    pub fn sorted_keys(mut self, sorted: bool) -> Self {
+        self.style.sorted_keys = sorted;
+        self
+    }
+
+    /// Annotate boolean and null parameters as `(bool)true` and `(null)`,
+    /// so they can't be mistaken for the strings `"true"` or `"null"` by
+    /// tools parsing the output. Off by default.
+    pub fn with_typed_values(mut self, typed: bool) -> Self {
+        self.style.typed_values = typed;
+        self
+    }
+
+    /// Set how binary parameters are rendered, prefixed with `hex:` or
+    /// `base64:`. Defaults to [`BytesEncoding::Hex`].
+    pub fn with_bytes_encoding(mut self, encoding: BytesEncoding) -> Self {
+        self.style.bytes = encoding;
+        self
+    }
+
+    /// Render errors using a custom template.
+    ///
+    /// The placeholders `{code}`, `{name}`, `{message}` and `{parameters}`
+    /// are substituted; any other `{...}` token is rendered literally. The
+    /// template replaces the whole output, so the default parameter,
+    /// documentation and trace suffixes are not appended.
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Write the response parameters as `{key: value, ...}`.
+    fn write_parameters(
+        &self,
+        w: &mut dyn std::fmt::Write,
+        params: &HashMap<String, ParameterValue>,
+    ) -> std::fmt::Result {
+        write_plain_entries(w, params, self.style)
+    }
+
+    /// Write `template` with its known placeholders substituted.
+    fn write_template(
+        &self,
+        w: &mut dyn std::fmt::Write,
+        template: &str,
+        error: &ServiceError,
+    ) -> std::fmt::Result {
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            w.write_str(&rest[..start])?;
+            let tail = &rest[start..];
+            let Some(end) = tail.find('}') else {
+                // Unterminated brace, the rest is literal
+                return w.write_str(tail);
+            };
+            if let Some(next) = tail[1..end].find('{') {
+                // Stray brace, rescan from the next one
+                w.write_str(&tail[..next + 1])?;
+                rest = &tail[next + 1..];
+                continue;
+            }
+            match &tail[1..end] {
+                "code" => write!(w, "{}", error.code)?,
+                "name" => w.write_str(&error.name)?,
+                "message" => w.write_str(&error.response_message())?,
+                "parameters" => {
+                    if let Some(params) = error.response_parameters() {
+                        self.write_parameters(w, &params)?;
+                    }
+                }
+                _ => w.write_str(&tail[..=end])?,
+            }
+            rest = &tail[end + 1..];
+        }
+
+        w.write_str(rest)
     }
 }
 
-#[cfg(feature = "json")]
-impl ResponseBuilder for JsonResponseBuilder {
+impl ResponseBuilder for PlainTextResponseBuilder {
+    fn content_type(&self) -> &'static str {
+        MediaType::Text.content_type(self.charset)
+    }
+
     fn build(&self, error: &ServiceError) -> (String, &'static str) {
-        let response_body = JsonResponseBody {
-            code: error.code,
-            name: error.name.clone(),
-            message: error.format_message(),
-            parameters: error.parameters.clone(),
-        };
+        let mut text = String::new();
+        let content_type = self
+            .write_to(error, &mut text)
+            .expect("writing to a String cannot fail");
+        (text, content_type)
+    }
 
-        let json = serde_json::to_string(&response_body).unwrap_or_else(|_| {
-            format!("{{\"error\":\"Failed to serialize error {}\"}}", error.code)
-        });
+    fn write_to(
+        &self,
+        error: &ServiceError,
+        w: &mut dyn std::fmt::Write,
+    ) -> Result<&'static str, std::fmt::Error> {
+        if let Some(template) = &self.template {
+            self.write_template(w, template, error)?;
+            return Ok(self.content_type());
+        }
+
+        write!(
+            w,
+            "Error {}: {} - {}",
+            error.code,
+            error.name,
+            error.response_message()
+        )?;
+        if let Some(params) = error.response_parameters() {
+            w.write_str(" (Parameters: ")?;
+            self.write_parameters(w, &params)?;
+            w.write_char(')')?;
+        }
+        if let Some(doc_url) = &error.doc_url {
+            write!(w, " (see: {})", doc_url)?;
+        }
+        if let Some(trace_id) = &error.trace_id {
+            write!(w, " (trace: {})", trace_id)?;
+        }
+        Ok(self.content_type())
+    }
+}
+
+/// A placeholder that [`ServiceError::try_format_message`] could not resolve
+/// in strict mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedPlaceholder(pub String);
+
+impl Display for UnresolvedPlaceholder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unresolved placeholder: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnresolvedPlaceholder {}
+
+/// An invalid HTTP status code passed to [`ServiceError::try_new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidStatus(pub u16);
+
+impl Display for InvalidStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid HTTP status: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidStatus {}
+
+/// A code or namespace prefix rejected by
+/// [`ServiceError::try_with_namespace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidNamespace {
+    /// The error code is not below [`NAMESPACE_SIZE`].
+    CodeOutOfRange(u32),
+    /// The namespaced code does not fit into a `u32`.
+    Overflow { prefix: u32, code: u32 },
+}
+
+impl Display for InvalidNamespace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidNamespace::CodeOutOfRange(code) => write!(
+                f,
+                "error code {} is outside the namespace size {}",
+                code, NAMESPACE_SIZE
+            ),
+            InvalidNamespace::Overflow { prefix, code } => write!(
+                f,
+                "namespaced error code overflows u32: prefix {}, code {}",
+                prefix, code
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidNamespace {}
+
+/// A problem found by [`ServiceError::assert_valid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// The HTTP status is not a valid status code.
+    InvalidStatus(u16),
+    /// The HTTP status is valid but not a 4xx or 5xx error status.
+    NonErrorStatus(u16),
+    /// The error name is empty.
+    EmptyName,
+    /// The number of bound arguments does not match the placeholders in the
+    /// message.
+    ArgumentCountMismatch {
+        placeholders: usize,
+        arguments: usize,
+    },
+    /// The error code lies outside all ranges set with
+    /// [`register_code_ranges`].
+    CodeOutOfRange(u32),
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::InvalidStatus(status) => write!(f, "invalid HTTP status: {}", status),
+            ValidationIssue::NonErrorStatus(status) => {
+                write!(f, "HTTP status is not an error status: {}", status)
+            }
+            ValidationIssue::EmptyName => write!(f, "error name is empty"),
+            ValidationIssue::ArgumentCountMismatch {
+                placeholders,
+                arguments,
+            } => write!(
+                f,
+                "message expects {} argument(s) but {} were bound",
+                placeholders, arguments
+            ),
+            ValidationIssue::CodeOutOfRange(code) => {
+                write!(f, "error code is outside the registered ranges: {}", code)
+            }
+        }
+    }
+}
+
+/// An error raised while populating an [`ErrorRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    /// The catalog could not be parsed.
+    InvalidCatalog(String),
+    /// An error definition with this code has already been registered.
+    DuplicateCode(u32),
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryError::InvalidCatalog(reason) => write!(f, "invalid error catalog: {}", reason),
+            RegistryError::DuplicateCode(code) => write!(f, "duplicate error code: {}", code),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// A collection of error definitions keyed by their error code.
+#[derive(Debug, Clone, Default)]
+pub struct ErrorRegistry {
+    errors: HashMap<u32, ServiceError<'static>>,
+}
+
+impl ErrorRegistry {
+    pub fn new() -> Self {
+        Self {
+            errors: HashMap::new(),
+        }
+    }
+
+    /// Load error definitions from a JSON array of
+    /// `{"code", "name", "status", "message"}` objects.
+    #[cfg(feature = "json")]
+    pub fn load_from_json(json: &str) -> Result<Self, RegistryError> {
+        let definitions: Vec<ErrorDefinition> = serde_json::from_str(json)
+            .map_err(|err| RegistryError::InvalidCatalog(err.to_string()))?;
+
+        let mut registry = Self::new();
+        for definition in definitions {
+            registry.register(ServiceError {
+                name: Cow::Owned(definition.name),
+                message: Cow::Owned(definition.message),
+                ..ServiceError::new(definition.code, "", definition.status, "")
+            })?;
+        }
+        Ok(registry)
+    }
+
+    /// Register an error definition under its code.
+    ///
+    /// Fails with [`RegistryError::DuplicateCode`] if the code is already
+    /// registered; the existing definition is kept. As with any clone, a
+    /// response builder set on the definition is not carried over to the
+    /// errors returned by [`ErrorRegistry::get`].
+    pub fn register(&mut self, error: ServiceError<'static>) -> Result<(), RegistryError> {
+        if self.errors.contains_key(&error.code) {
+            return Err(RegistryError::DuplicateCode(error.code));
+        }
+        self.errors.insert(error.code, error);
+        Ok(())
+    }
 
-        (json, "application/json")
+    /// Get a fresh copy of the error registered under `code`, ready to be
+    /// customized with arguments and parameters.
+    pub fn get(&self, code: u32) -> Option<ServiceError<'static>> {
+        self.errors.get(&code).cloned()
     }
 }
 
+/// Global catalog of error definitions used by [`ServiceError::from_code`].
+static CODE_CATALOG: RwLock<Option<Arc<ErrorRegistry>>> = RwLock::new(None);
+
+/// Register the global catalog of error definitions used by
+/// [`ServiceError::from_code`].
+/// This should be called once at application startup.
+///
+/// Replaces any previously registered catalog and returns it.
+pub fn register_code_catalog(catalog: ErrorRegistry) -> Option<Arc<ErrorRegistry>> {
+    CODE_CATALOG
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .replace(Arc::new(catalog))
+}
+
+/// Global ranges of valid error codes checked by [`ServiceError::assert_valid`].
+static CODE_RANGES: RwLock<Option<Arc<Vec<Range<u32>>>>> = RwLock::new(None);
+
+/// Register the ranges of error codes that [`ServiceError::assert_valid`]
+/// accepts, e.g. the namespaces of the services an application owns
+/// (`prefix * NAMESPACE_SIZE..(prefix + 1) * NAMESPACE_SIZE`). Without
+/// registered ranges, or with an empty list, every code is accepted.
+/// This should be called once at application startup.
+///
+/// Replaces any previously registered ranges and returns them.
+pub fn register_code_ranges(ranges: Vec<Range<u32>>) -> Option<Arc<Vec<Range<u32>>>> {
+    CODE_RANGES
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .replace(Arc::new(ranges))
+}
+
+/// Global error names by HTTP status used by [`ServiceError::from_status`].
+static STATUS_NAMES: RwLock<Option<Arc<HashMap<u16, String>>>> = RwLock::new(None);
+
+/// Register custom error names for HTTP statuses, e.g. `422` to
+/// `"UNPROCESSABLE"`, used by [`ServiceError::from_status`] in place of the
+/// name derived from the canonical reason phrase.
+/// This should be called once at application startup.
+///
+/// Replaces any previously registered names and returns them.
+pub fn register_status_names(names: HashMap<u16, String>) -> Option<Arc<HashMap<u16, String>>> {
+    STATUS_NAMES
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .replace(Arc::new(names))
+}
+
+/// A single entry of a serialized error catalog.
 #[cfg(feature = "json")]
-#[derive(Debug, Clone, Serialize)]
-struct JsonResponseBody<'a> {
+#[derive(Debug, Deserialize)]
+struct ErrorDefinition {
     code: u32,
-    #[serde(borrow)]
-    name: Cow<'a, str>,
+    name: String,
+    status: u16,
     message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    parameters: Option<HashMap<String, ParameterValue>>,
 }
 
-/// A simple plain text response builder.
+/// A response builder that renders errors with messages localized from a
+/// [`MessageCatalog`].
+///
+/// The localized message is handed to an inner builder, which defaults to
+/// [`PlainTextResponseBuilder`].
+#[derive(Debug)]
+pub struct LocalizedResponseBuilder {
+    catalog: Arc<MessageCatalog>,
+    locale: String,
+    inner: Box<dyn ResponseBuilder>,
+}
+
+impl LocalizedResponseBuilder {
+    pub fn new(catalog: impl Into<Arc<MessageCatalog>>, locale: impl Into<String>) -> Self {
+        Self {
+            catalog: catalog.into(),
+            locale: locale.into(),
+            inner: Box::new(PlainTextResponseBuilder::new()),
+        }
+    }
+
+    /// Set the builder used to render the localized error.
+    pub fn with_inner(mut self, builder: impl ResponseBuilder + 'static) -> Self {
+        self.inner = Box::new(builder);
+        self
+    }
+
+    /// Copy `error` with its message localized and its message and detail
+    /// already formatted.
+    fn localized<'e>(&self, error: &ServiceError<'e>) -> ServiceError<'e> {
+        let mut localized = error.clone();
+        localized.message = Cow::Owned(error.localize(&self.catalog, &self.locale).into_owned());
+        localized.lazy_message = None;
+        localized.detail = error.format_detail().map(Cow::Owned);
+        localized.arguments.clear();
+        localized
+    }
+}
+
+impl ResponseBuilder for LocalizedResponseBuilder {
+    fn content_type(&self) -> &'static str {
+        self.inner.content_type()
+    }
+
+    fn build(&self, error: &ServiceError) -> (String, &'static str) {
+        self.inner.build(&self.localized(error))
+    }
+
+    fn build_with_parts(&self, error: &ServiceError, parts: &Parts) -> (String, &'static str) {
+        self.inner.build_with_parts(&self.localized(error), parts)
+    }
+}
+
+/// A response builder that delegates to an inner builder chosen by the
+/// class of the error's HTTP status.
+///
+/// Classes are identified by their leading digit, e.g. `4` for 4xx. Errors
+/// whose class has no route are rendered by the fallback builder, which
+/// defaults to [`PlainTextResponseBuilder`]. Responses carry the
+/// content-type of the builder they were routed to;
+/// [`ResponseBuilder::content_type`], which has no error to route, reports
+/// the one of the fallback builder.
+#[derive(Debug)]
+pub struct StatusRoutingResponseBuilder {
+    routes: HashMap<u16, Box<dyn ResponseBuilder>>,
+    fallback: Box<dyn ResponseBuilder>,
+}
+
+impl Default for StatusRoutingResponseBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatusRoutingResponseBuilder {
+    pub fn new() -> Self {
+        Self {
+            routes: HashMap::new(),
+            fallback: Box::new(PlainTextResponseBuilder::new()),
+        }
+    }
+
+    /// Render errors of the given status class (e.g. `4` for 4xx) with
+    /// `builder`.
+    pub fn route(mut self, class: u16, builder: impl ResponseBuilder + 'static) -> Self {
+        self.routes.insert(class, Box::new(builder));
+        self
+    }
+
+    /// Set the builder used for status classes without a route.
+    pub fn with_fallback(mut self, builder: impl ResponseBuilder + 'static) -> Self {
+        self.fallback = Box::new(builder);
+        self
+    }
+
+    /// The builder routed to by the status class of `error`.
+    fn builder_for(&self, error: &ServiceError) -> &dyn ResponseBuilder {
+        self.routes
+            .get(&(error.http_status / 100))
+            .unwrap_or(&self.fallback)
+            .as_ref()
+    }
+}
+
+impl ResponseBuilder for StatusRoutingResponseBuilder {
+    fn build(&self, error: &ServiceError) -> (String, &'static str) {
+        self.builder_for(error).build(error)
+    }
+
+    fn build_with_parts(&self, error: &ServiceError, parts: &Parts) -> (String, &'static str) {
+        self.builder_for(error).build_with_parts(error, parts)
+    }
+
+    fn content_type(&self) -> &'static str {
+        self.fallback.content_type()
+    }
+
+    fn write_to(
+        &self,
+        error: &ServiceError,
+        w: &mut dyn std::fmt::Write,
+    ) -> Result<&'static str, std::fmt::Error> {
+        self.builder_for(error).write_to(error, w)
+    }
+}
+
+/// A response builder that tries a list of builders in order and uses the
+/// first one that renders the error successfully.
+///
+/// A builder fails when [`ResponseBuilder::write_to`] returns an error, as
+/// [`JsonResponseBuilder`] does when serialization fails. If every builder
+/// fails, or the list is empty, the error is rendered as plain text.
+#[derive(Debug)]
+pub struct FallbackResponseBuilder {
+    builders: Vec<Box<dyn ResponseBuilder>>,
+}
+
+impl FallbackResponseBuilder {
+    pub fn new(builders: Vec<Box<dyn ResponseBuilder>>) -> Self {
+        Self { builders }
+    }
+}
+
+impl ResponseBuilder for FallbackResponseBuilder {
+    fn build(&self, error: &ServiceError) -> (String, &'static str) {
+        for builder in &self.builders {
+            let mut body = String::new();
+            if let Ok(content_type) = builder.write_to(error, &mut body) {
+                return (body, content_type);
+            }
+        }
+        PlainTextResponseBuilder::new().build(error)
+    }
+}
+
+/// A response builder that renders a minimal HTML error page.
+///
+/// All interpolated values are HTML-escaped.
 #[derive(Debug, Clone)]
-pub struct PlainTextResponseBuilder;
+pub struct HtmlResponseBuilder {
+    title: Option<String>,
+    charset: Option<Charset>,
+}
 
-impl Default for PlainTextResponseBuilder {
+impl Default for HtmlResponseBuilder {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl PlainTextResponseBuilder {
+impl HtmlResponseBuilder {
     pub fn new() -> Self {
-        Self
+        Self {
+            title: None,
+            charset: Some(Charset::Utf8),
+        }
+    }
+
+    /// Set the `charset` parameter of the content-type, or send a bare
+    /// `text/html` with `None`. Defaults to [`Charset::Utf8`].
+    ///
+    /// This only changes the header; the body is always UTF-8.
+    pub fn with_charset(mut self, charset: Option<Charset>) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Set the page title. Defaults to the status code and error name.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
     }
 }
 
-impl ResponseBuilder for PlainTextResponseBuilder {
+impl ResponseBuilder for HtmlResponseBuilder {
+    fn content_type(&self) -> &'static str {
+        MediaType::Html.content_type(self.charset)
+    }
+
     fn build(&self, error: &ServiceError) -> (String, &'static str) {
-        let text = if let Some(ref params) = error.parameters {
-            let param_display: Vec<String> = params
-                .iter()
-                .map(|(k, v)| format!("{}: {}", k, v))
-                .collect();
-            format!(
-                "Error {}: {} - {} (Parameters: {{{}}})",
-                error.code,
-                error.name,
-                error.format_message(),
-                param_display.join(", ")
-            )
-        } else {
-            format!(
-                "Error {}: {} - {}",
-                error.code,
-                error.name,
-                error.format_message()
-            )
+        let title = match &self.title {
+            Some(title) => escape_html(title),
+            None => format!("{} {}", error.http_status, escape_html(&error.name)),
         };
-        (text, "text/plain")
+
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+             <style>body{{font-family:sans-serif;margin:4em auto;max-width:40em;color:#333}}\
+             h1{{font-size:1.5em}}code{{color:#888}}</style>\n</head>\n<body>\n\
+             <h1>{status} {name}</h1>\n<p>{message}</p>\n",
+            title = title,
+            status = error.http_status,
+            name = escape_html(&error.name),
+            message = escape_html(&error.response_message()),
+        );
+        if let Some(detail) = error.response_detail() {
+            html.push_str(&format!("<p>{}</p>\n", escape_html(&detail)));
+        }
+        if let Some(params) = error.response_parameters() {
+            html.push_str("<dl>\n");
+            for (key, value) in params.iter() {
+                html.push_str(&format!(
+                    "<dt>{}</dt><dd>{}</dd>\n",
+                    escape_html(key),
+                    escape_html(&value.to_string())
+                ));
+            }
+            html.push_str("</dl>\n");
+        }
+        if let Some(trace_id) = &error.trace_id {
+            html.push_str(&format!(
+                "<p><code>trace: {}</code></p>\n",
+                escape_html(trace_id)
+            ));
+        }
+        html.push_str("</body>\n</html>\n");
+
+        (html, self.content_type())
     }
 }
+
+/// Escape the characters that are significant in HTML text and attributes.
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A tower layer that renders [`ServiceError`]s found in response
+/// extensions.
+///
+/// Handlers can return an error as a response extension, e.g. as
+/// `axum::Extension(error)`, instead of rendering it themselves. The layer
+/// removes it from the extensions and replaces the whole response with the
+/// error rendered by the configured builder. Responses without an error
+/// pass through untouched.
+#[derive(Debug, Clone)]
+pub struct ServiceErrorLayer {
+    builder: Arc<dyn ResponseBuilder>,
+}
+
+impl ServiceErrorLayer {
+    pub fn new(builder: impl ResponseBuilder + 'static) -> Self {
+        Self {
+            builder: Arc::new(builder),
+        }
+    }
+}
+
+impl<S> tower_layer::Layer<S> for ServiceErrorLayer {
+    type Service = ServiceErrorService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ServiceErrorService {
+            inner,
+            builder: self.builder.clone(),
+        }
+    }
+}
+
+/// The service produced by [`ServiceErrorLayer`].
+#[derive(Debug, Clone)]
+pub struct ServiceErrorService<S> {
+    inner: S,
+    builder: Arc<dyn ResponseBuilder>,
+}
+
+impl<S, Request> tower_service::Service<Request> for ServiceErrorService<S>
+where
+    S: tower_service::Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Response, S::Error>> + Send + 'static>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let future = self.inner.call(request);
+        let builder = self.builder.clone();
+        Box::pin(async move {
+            let mut response = future.await?;
+            match response.extensions_mut().remove::<ServiceError<'static>>() {
+                Some(error) => Ok(error.with_response_builder(builder).into_response()),
+                None => Ok(response),
+            }
+        })
+    }
+}
+
+/// Re-exports of the commonly used types, traits and macros.
+///
+/// ```
+/// use axum_service_errors::prelude::*;
+///
+/// let error = ServiceError::new(2001, "NOT_FOUND", 404, "User {0} not found")
+///     .bind("alice")
+///     .parameter("request", param_object! { "roles" => param_array!["admin"] })
+///     .severity(Severity::Warning);
+///
+/// let (body, _) = PlainTextResponseBuilder::new().build(&error);
+/// assert!(body.starts_with("Error 2001: NOT_FOUND - User alice not found"));
+/// ```
+pub mod prelude {
+    #[cfg(feature = "derive")]
+    pub use crate::ServiceErrorEnum;
+    #[cfg(feature = "yaml")]
+    pub use crate::YamlResponseBuilder;
+    pub use crate::{
+        BytesEncoding, Category, Charset, ErrorRegistry, FallbackResponseBuilder,
+        HtmlResponseBuilder, LocalizedResponseBuilder, MessageCatalog, NAMESPACE_SIZE,
+        ParameterValue, PlainTextResponseBuilder, ResponseBuilder, ResponseFormat, RetryAfter,
+        ServiceError, ServiceErrorLayer, ServiceResult, Severity, StatusRoutingResponseBuilder,
+        namespace_of, param_array, param_object, register_code_catalog, register_code_ranges,
+        register_status_names, reset_default_response_builder, reset_default_response_format,
+        set_default_response_builder, set_default_response_format,
+    };
+    #[cfg(feature = "json")]
+    pub use crate::{DebugResponseBuilder, EnvelopeResponseBuilder, JsonResponseBuilder};
+}
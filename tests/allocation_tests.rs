@@ -0,0 +1,49 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+#[cfg(feature = "json")]
+use axum_service_errors::JsonResponseBuilder;
+use axum_service_errors::{PlainTextResponseBuilder, ResponseBuilder, ServiceError};
+
+/// Counts the allocations made by the current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_while_writing(builder: &dyn ResponseBuilder, error: &ServiceError) -> usize {
+    let mut body = String::with_capacity(1024);
+    let before = ALLOCATIONS.with(Cell::get);
+    builder.write_to(error, &mut body).unwrap();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn test_builders_render_plain_messages_without_allocating() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    assert_eq!(
+        allocations_while_writing(&PlainTextResponseBuilder::new(), &error),
+        0
+    );
+    #[cfg(feature = "json")]
+    assert_eq!(
+        allocations_while_writing(&JsonResponseBuilder::new(), &error),
+        0
+    );
+}
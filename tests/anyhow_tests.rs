@@ -0,0 +1,37 @@
+#![cfg(feature = "anyhow")]
+
+use anyhow::{Context, anyhow};
+use axum_service_errors::ServiceError;
+
+#[test]
+fn test_from_anyhow_error() {
+    let error: ServiceError<'static> = anyhow!("boom").into();
+
+    assert_eq!(error.code, 5000);
+    assert_eq!(error.http_status, 500);
+    assert_eq!(error.message, "boom");
+    assert_eq!(error.source().unwrap().to_string(), "boom");
+}
+
+#[test]
+fn test_from_anyhow_error_uses_top_level_context() {
+    let result: anyhow::Result<()> = Err(anyhow!("connection refused"));
+    let error: ServiceError<'static> = result.context("failed to load user").unwrap_err().into();
+
+    assert_eq!(error.message, "failed to load user");
+    assert_eq!(
+        error.source().unwrap().source().unwrap().to_string(),
+        "connection refused"
+    );
+}
+
+#[test]
+fn test_question_mark_converts_anyhow_error() {
+    #[allow(clippy::result_large_err)]
+    fn handler() -> Result<(), ServiceError<'static>> {
+        Err(anyhow!("boom"))?;
+        Ok(())
+    }
+
+    assert_eq!(handler().unwrap_err().http_status, 500);
+}
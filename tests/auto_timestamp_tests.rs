@@ -0,0 +1,31 @@
+#![cfg(all(feature = "auto-timestamp", feature = "json"))]
+
+use axum::{body::to_bytes, response::IntoResponse};
+use axum_service_errors::{JsonResponseBuilder, ServiceError};
+
+#[tokio::test]
+async fn test_into_response_stamps_missing_timestamp() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+        .with_response_builder(JsonResponseBuilder::new());
+
+    let response = error.into_response();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(json["timestamp"].as_str().unwrap().ends_with('Z'));
+}
+
+#[tokio::test]
+async fn test_into_response_keeps_existing_timestamp() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let mut error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+        .with_response_builder(JsonResponseBuilder::new());
+    error.timestamp = Some(UNIX_EPOCH + Duration::from_secs(86_400));
+
+    let response = error.into_response();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["timestamp"], "1970-01-02T00:00:00.000Z");
+}
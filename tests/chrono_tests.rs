@@ -0,0 +1,67 @@
+#![cfg(feature = "chrono")]
+
+use axum_service_errors::{ParameterValue, ServiceError};
+use chrono::{TimeZone, Utc};
+
+#[test]
+fn test_datetime_from() {
+    let timestamp = Utc.with_ymd_and_hms(2025, 7, 3, 12, 30, 0).unwrap();
+
+    assert_eq!(
+        ParameterValue::from(timestamp),
+        ParameterValue::DateTime(timestamp)
+    );
+}
+
+#[test]
+fn test_datetime_display() {
+    let value = ParameterValue::from(Utc.with_ymd_and_hms(2025, 7, 3, 12, 30, 0).unwrap());
+
+    assert_eq!(format!("{}", value), "2025-07-03T12:30:00Z");
+    assert_eq!(format!("{:#}", value), "\"2025-07-03T12:30:00Z\"");
+}
+
+#[test]
+fn test_datetime_serialization() {
+    let timestamp = Utc.with_ymd_and_hms(2025, 7, 3, 12, 30, 0).unwrap();
+    let error = ServiceError::new(1003, "ACCOUNT_LOCKED", 423, "Account locked")
+        .parameter("locked_until", timestamp);
+
+    let serialized = serde_json::to_string(&error).unwrap();
+    assert!(serialized.contains("\"locked_until\":\"2025-07-03T12:30:00Z\""));
+
+    let value = serde_json::to_value(ParameterValue::from(timestamp)).unwrap();
+    let round_tripped: ParameterValue = serde_json::from_value(value).unwrap();
+    assert_eq!(
+        round_tripped.parse_datetimes(),
+        ParameterValue::DateTime(timestamp)
+    );
+}
+
+#[test]
+fn test_datetime_strings_deserialize_as_strings() {
+    // Deserialization does not depend on the `chrono` feature
+    let value: ParameterValue = serde_json::from_str("\"2025-07-03T12:30:00Z\"").unwrap();
+
+    assert_eq!(value, ParameterValue::from("2025-07-03T12:30:00Z"));
+}
+
+#[test]
+fn test_parse_datetimes() {
+    let timestamp = Utc.with_ymd_and_hms(2025, 7, 3, 12, 30, 0).unwrap();
+    let value: ParameterValue = serde_json::from_str(
+        r#"{"locked_until": "2025-07-03T14:30:00+02:00", "reason": ["not a date"]}"#,
+    )
+    .unwrap();
+
+    let parsed = value.parse_datetimes();
+
+    let ParameterValue::Object(entries) = parsed else {
+        panic!("expected an object");
+    };
+    assert_eq!(entries["locked_until"], ParameterValue::DateTime(timestamp));
+    assert_eq!(
+        entries["reason"],
+        ParameterValue::Array(vec![ParameterValue::from("not a date")])
+    );
+}
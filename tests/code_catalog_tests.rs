@@ -0,0 +1,26 @@
+use axum_service_errors::{ErrorRegistry, ServiceError, register_code_catalog};
+
+// The code catalog is global, so the whole sequence runs in one test.
+#[test]
+fn test_from_code() {
+    assert!(ServiceError::from_code(2001).is_none());
+
+    let mut catalog = ErrorRegistry::new();
+    catalog
+        .register(ServiceError::new(
+            2001,
+            "USER_NOT_FOUND",
+            404,
+            "User {0} not found",
+        ))
+        .unwrap();
+    let previous = register_code_catalog(catalog);
+    assert!(previous.is_none());
+
+    let error = ServiceError::from_code(2001).unwrap().bind("alice");
+    assert_eq!(error.name, "USER_NOT_FOUND");
+    assert_eq!(error.http_status, 404);
+    assert_eq!(error.format_message(), "User alice not found");
+
+    assert!(ServiceError::from_code(9999).is_none());
+}
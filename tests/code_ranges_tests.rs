@@ -0,0 +1,37 @@
+use axum_service_errors::{NAMESPACE_SIZE, ServiceError, ValidationIssue, register_code_ranges};
+
+// The code ranges are global, so the whole sequence runs in one test.
+#[test]
+fn test_assert_valid_checks_registered_code_ranges() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+    assert_eq!(error.assert_valid(), Ok(()));
+
+    let previous = register_code_ranges(vec![1000..2000, 3 * NAMESPACE_SIZE..4 * NAMESPACE_SIZE]);
+    assert!(previous.is_none());
+
+    assert_eq!(error.assert_valid(), Ok(()));
+    assert_eq!(error.clone().with_namespace(3).assert_valid(), Ok(()));
+
+    let error = ServiceError::new(2001, "", 400, "Invalid input");
+    assert_eq!(
+        error.assert_valid(),
+        Err(vec![
+            ValidationIssue::EmptyName,
+            ValidationIssue::CodeOutOfRange(2001)
+        ])
+    );
+    assert_eq!(
+        ValidationIssue::CodeOutOfRange(2001).to_string(),
+        "error code is outside the registered ranges: 2001"
+    );
+
+    let previous = register_code_ranges(Vec::new());
+    assert_eq!(
+        ServiceError::new(2001, "NOT_FOUND", 404, "Missing").assert_valid(),
+        Ok(())
+    );
+    assert_eq!(
+        *previous.unwrap(),
+        vec![1000..2000, 3 * NAMESPACE_SIZE..4 * NAMESPACE_SIZE]
+    );
+}
@@ -0,0 +1,40 @@
+use axum::response::IntoResponse;
+use axum_service_errors::{
+    HtmlResponseBuilder, PlainTextResponseBuilder, ServiceError, reset_default_response_builder,
+    set_default_response_builder,
+};
+
+fn content_type() -> String {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+    let response = error.into_response();
+    response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+// The default builder is global, so the whole sequence runs in one test.
+#[test]
+fn test_set_reset_and_replace_default_builder() {
+    assert_eq!(content_type(), "text/plain; charset=utf-8");
+
+    let previous = set_default_response_builder(HtmlResponseBuilder::new());
+    assert!(previous.is_none());
+    assert_eq!(content_type(), "text/html; charset=utf-8");
+
+    let removed = reset_default_response_builder();
+    assert!(removed.is_some());
+    assert_eq!(content_type(), "text/plain; charset=utf-8");
+
+    set_default_response_builder(HtmlResponseBuilder::new());
+    let previous =
+        set_default_response_builder(PlainTextResponseBuilder::new().with_template("{name}"));
+    assert!(previous.is_some());
+
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+    let (body, _) = previous.unwrap().build(&error);
+    assert!(body.starts_with("<!DOCTYPE html>"));
+    assert_eq!(content_type(), "text/plain; charset=utf-8");
+
+    reset_default_response_builder();
+}
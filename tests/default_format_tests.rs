@@ -0,0 +1,37 @@
+#![cfg(feature = "json")]
+
+use axum::{body::to_bytes, response::IntoResponse};
+use axum_service_errors::{
+    ResponseFormat, ServiceError, reset_default_response_format, set_default_response_format,
+};
+
+// The default format is global, so the whole sequence runs in one test.
+#[tokio::test]
+async fn test_default_response_format_json() {
+    assert_eq!(set_default_response_format(ResponseFormat::Json), None);
+    assert_eq!(
+        set_default_response_format(ResponseFormat::Json),
+        Some(ResponseFormat::Json)
+    );
+
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+    let response = error.into_response();
+
+    assert_eq!(response.status(), 400);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/json; charset=utf-8"
+    );
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["code"], 1001);
+    assert_eq!(json["name"], "VALIDATION_ERROR");
+
+    assert_eq!(reset_default_response_format(), Some(ResponseFormat::Json));
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+    assert_eq!(
+        error.into_response().headers()["content-type"],
+        "text/plain; charset=utf-8"
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "derive")]
+
+use axum_service_errors::{ParameterValue, ServiceError, ServiceErrorEnum};
+
+#[derive(ServiceErrorEnum)]
+enum UserError {
+    #[error(code = 2000, status = 401, message = "Authentication required")]
+    Unauthenticated,
+    #[error(code = 2001, status = 404, message = "User {0} not found")]
+    NotFound(String),
+    #[error(
+        code = 2002,
+        status = 409,
+        name = "USER_CONFLICT",
+        message = "User {0} already has role {1}"
+    )]
+    RoleConflict { user: String, role: String },
+    #[error(code = 2003, status = 413, message = "{0} of {1} bytes uploaded")]
+    TooLarge(usize, u64),
+    #[error(code = 2004, status = 403, message = "Address {0} is blocked")]
+    Blocked(#[error(display)] std::net::IpAddr),
+}
+
+#[test]
+fn test_derive_unit_variant() {
+    let error: ServiceError<'static> = UserError::Unauthenticated.into();
+
+    assert_eq!(error.code, 2000);
+    assert_eq!(error.name, "UNAUTHENTICATED");
+    assert_eq!(error.http_status, 401);
+    assert_eq!(error.message, "Authentication required");
+    assert!(error.arguments.is_empty());
+}
+
+#[test]
+fn test_derive_binds_tuple_fields() {
+    let error: ServiceError<'static> = UserError::NotFound("alice".to_string()).into();
+
+    assert_eq!(error.code, 2001);
+    assert_eq!(error.name, "NOT_FOUND");
+    assert_eq!(error.http_status, 404);
+    assert_eq!(error.arguments, vec![ParameterValue::from("alice")]);
+}
+
+#[test]
+fn test_derive_binds_named_fields_with_custom_name() {
+    let error: ServiceError<'static> = UserError::RoleConflict {
+        user: "alice".to_string(),
+        role: "admin".to_string(),
+    }
+    .into();
+
+    assert_eq!(error.code, 2002);
+    assert_eq!(error.name, "USER_CONFLICT");
+    assert_eq!(error.http_status, 409);
+    assert_eq!(
+        error.arguments,
+        vec![ParameterValue::from("alice"), ParameterValue::from("admin")]
+    );
+}
+
+#[test]
+fn test_derive_binds_unsigned_fields() {
+    let error: ServiceError<'static> = UserError::TooLarge(10, 4096).into();
+
+    assert_eq!(
+        error.arguments,
+        vec![ParameterValue::Integer(10), ParameterValue::Integer(4096)]
+    );
+    assert_eq!(error.format_message(), "10 of 4096 bytes uploaded");
+}
+
+#[test]
+fn test_derive_binds_display_fields() {
+    let address = std::net::IpAddr::from([10, 0, 0, 1]);
+    let error: ServiceError<'static> = UserError::Blocked(address).into();
+
+    assert_eq!(error.arguments, vec![ParameterValue::from("10.0.0.1")]);
+    assert_eq!(error.format_message(), "Address 10.0.0.1 is blocked");
+}
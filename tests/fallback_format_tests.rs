@@ -0,0 +1,37 @@
+#![cfg(feature = "json")]
+
+use axum::response::IntoResponse;
+use axum_service_errors::{
+    JsonResponseBuilder, ServiceError, reset_default_response_builder, set_default_response_builder,
+};
+
+// The default builder is global, so the whole sequence runs in one test.
+#[test]
+fn test_use_fallback_format_bypasses_default_builder() {
+    set_default_response_builder(JsonResponseBuilder::new());
+
+    let response = ServiceError::new(5030, "UNAVAILABLE", 503, "Not ready").into_response();
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/json; charset=utf-8"
+    );
+
+    let response = ServiceError::new(5030, "UNAVAILABLE", 503, "Not ready")
+        .use_fallback_format()
+        .into_response();
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; charset=utf-8"
+    );
+
+    let response = ServiceError::new(5030, "UNAVAILABLE", 503, "Not ready")
+        .with_response_builder(JsonResponseBuilder::new())
+        .use_fallback_format()
+        .into_response();
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; charset=utf-8"
+    );
+
+    reset_default_response_builder();
+}
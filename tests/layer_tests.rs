@@ -0,0 +1,54 @@
+use axum::{
+    Extension, Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+    routing::get,
+};
+use axum_service_errors::{HtmlResponseBuilder, ServiceError, ServiceErrorLayer};
+use tower::ServiceExt;
+
+fn app() -> Router {
+    Router::new()
+        .route(
+            "/users/alice",
+            get(|| async {
+                Extension(ServiceError::new(2001, "NOT_FOUND", 404, "User not found"))
+            }),
+        )
+        .route("/health", get(|| async { "ok" }))
+        .layer(ServiceErrorLayer::new(HtmlResponseBuilder::new()))
+}
+
+async fn get_request(uri: &str) -> axum::response::Response {
+    app()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_layer_renders_error_from_extensions() {
+    let response = get_request("/users/alice").await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/html; charset=utf-8"
+    );
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("User not found"));
+}
+
+#[tokio::test]
+async fn test_layer_passes_through_other_responses() {
+    let response = get_request("/health").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; charset=utf-8"
+    );
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, "ok");
+}
@@ -0,0 +1,66 @@
+use axum_service_errors::{
+    LocalizedResponseBuilder, MessageCatalog, ResponseBuilder, ServiceError,
+};
+
+fn catalog() -> MessageCatalog {
+    MessageCatalog::new()
+        .message(1001, "de", "Ungültige Eingabe für Feld {0}")
+        .message(1001, "fr-CA", "Entrée invalide pour le champ {0}")
+}
+
+#[test]
+fn test_localize_hit() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input for field {0}")
+        .bind("email");
+
+    assert_eq!(
+        error.localize(&catalog(), "de"),
+        "Ungültige Eingabe für Feld email"
+    );
+    assert_eq!(
+        error.localize(&catalog(), "FR-ca"),
+        "Entrée invalide pour le champ email"
+    );
+}
+
+#[test]
+fn test_localize_regional_locale_uses_primary_language() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input for field {0}")
+        .bind("email");
+
+    assert_eq!(
+        error.localize(&catalog(), "de-AT"),
+        "Ungültige Eingabe für Feld email"
+    );
+}
+
+#[test]
+fn test_localize_missing_locale_falls_back_to_message() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input for field {0}")
+        .bind("email");
+
+    assert_eq!(
+        error.localize(&catalog(), "es"),
+        "Invalid input for field email"
+    );
+}
+
+#[test]
+fn test_localize_missing_code_falls_back_to_message() {
+    let error = ServiceError::new(2002, "NOT_FOUND", 404, "Resource not found");
+
+    assert_eq!(error.localize(&catalog(), "de"), "Resource not found");
+}
+
+#[test]
+fn test_localized_response_builder() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input for field {0}")
+        .bind("email");
+
+    let builder = LocalizedResponseBuilder::new(catalog(), "de");
+    let (body, content_type) = builder.build(&error);
+
+    assert_eq!(content_type, "text/plain; charset=utf-8");
+    assert!(body.contains("Error 1001"));
+    assert!(body.contains("Ungültige Eingabe für Feld email"));
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "metrics")]
+
+use axum::response::IntoResponse;
+use axum_service_errors::ServiceError;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+#[test]
+fn test_into_response_increments_counter() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+
+    metrics::with_local_recorder(&recorder, || {
+        let _ = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input").into_response();
+        let _ = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input").into_response();
+        let _ = ServiceError::new(2001, "NOT_FOUND", 404, "Not found").into_response();
+    });
+
+    let counters: Vec<_> = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, value)| {
+            let key = key.key();
+            let labels: Vec<_> = key
+                .labels()
+                .map(|label| (label.key().to_string(), label.value().to_string()))
+                .collect();
+            (key.name().to_string(), labels, value)
+        })
+        .collect();
+
+    let count_for = |code: &str, name: &str| {
+        counters
+            .iter()
+            .find(|(metric, labels, _)| {
+                metric == "service_errors_total"
+                    && labels.contains(&("code".to_string(), code.to_string()))
+                    && labels.contains(&("name".to_string(), name.to_string()))
+            })
+            .map(|(_, _, value)| value)
+    };
+
+    assert_eq!(
+        count_for("1001", "VALIDATION_ERROR"),
+        Some(&DebugValue::Counter(2))
+    );
+    assert_eq!(
+        count_for("2001", "NOT_FOUND"),
+        Some(&DebugValue::Counter(1))
+    );
+}
@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+
+use axum_service_errors::{ParameterValue, param_array, param_object};
+
+#[test]
+fn test_len_of_array() {
+    let value = param_array!["a", 1, true];
+
+    assert_eq!(value.len(), Some(3));
+    assert!(!value.is_empty());
+    assert!(ParameterValue::Array(vec![]).is_empty());
+}
+
+#[test]
+fn test_len_of_object() {
+    let value = param_object! { "min" => 1, "max" => 10 };
+
+    assert_eq!(value.len(), Some(2));
+    assert!(!value.is_empty());
+    assert!(param_object! {}.is_empty());
+}
+
+#[test]
+fn test_len_of_scalar_is_none() {
+    assert_eq!(ParameterValue::from("hello").len(), None);
+    assert_eq!(ParameterValue::from(42).len(), None);
+    assert_eq!(ParameterValue::Null.len(), None);
+    assert!(!ParameterValue::from("").is_empty());
+}
+
+#[test]
+fn test_iter_array() {
+    let value = param_array!["a", "b", "c"];
+
+    let items: Vec<String> = value.iter_array().unwrap().map(|v| v.to_string()).collect();
+    assert_eq!(items, vec!["a", "b", "c"]);
+    assert!(value.iter_object().is_none());
+}
+
+#[test]
+fn test_iter_object() {
+    let value = param_object! { "min" => 1, "max" => 10 };
+
+    let mut entries: Vec<(&String, &ParameterValue)> = value.iter_object().unwrap().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    assert_eq!(
+        entries,
+        vec![
+            (&"max".to_string(), &ParameterValue::Integer(10)),
+            (&"min".to_string(), &ParameterValue::Integer(1)),
+        ]
+    );
+    assert!(value.iter_array().is_none());
+}
+
+#[test]
+fn test_from_option_some() {
+    assert_eq!(ParameterValue::from(Some(42)), ParameterValue::Integer(42));
+    assert_eq!(
+        ParameterValue::from(Some("email")),
+        ParameterValue::String("email".to_string())
+    );
+}
+
+#[test]
+fn test_from_option_none() {
+    assert_eq!(ParameterValue::from(None::<i64>), ParameterValue::Null);
+}
+
+#[test]
+fn test_from_string_map() {
+    let mut map = HashMap::new();
+    map.insert("field".to_string(), "email".to_string());
+    map.insert("reason".to_string(), "malformed".to_string());
+
+    assert_eq!(
+        ParameterValue::from(map),
+        param_object! { "field" => "email", "reason" => "malformed" }
+    );
+}
+
+#[test]
+fn test_display_string() {
+    let value = ParameterValue::from("say \"hi\"\n");
+
+    assert_eq!(format!("{}", value), "say \"hi\"\n");
+    assert_eq!(format!("{:#}", value), r#""say \"hi\"\n""#);
+}
+
+#[test]
+fn test_display_distinguishes_strings_from_numbers_in_alternate_mode() {
+    assert_eq!(format!("{}", ParameterValue::from("1")), "1");
+    assert_eq!(format!("{}", ParameterValue::from(1)), "1");
+    assert_eq!(format!("{:#}", ParameterValue::from("1")), "\"1\"");
+    assert_eq!(format!("{:#}", ParameterValue::from(1)), "1");
+}
+
+#[test]
+fn test_display_nested_object() {
+    let value = param_object! {
+        "user" => param_object! {
+            "roles" => param_array!["admin", 7, ParameterValue::Null],
+        },
+    };
+
+    assert_eq!(format!("{}", value), "{user: {roles: [admin, 7, null]}}");
+    assert_eq!(
+        format!("{:#}", value),
+        r#"{"user": {"roles": ["admin", 7, null]}}"#
+    );
+
+    let parsed: serde_json::Value = serde_json::from_str(&format!("{:#}", value)).unwrap();
+    assert_eq!(parsed["user"]["roles"][0], "admin");
+}
+
+#[test]
+fn test_try_from_string() {
+    let value: String = ParameterValue::from("hello").try_into().unwrap();
+    assert_eq!(value, "hello");
+
+    let error = String::try_from(ParameterValue::from(42)).unwrap_err();
+    assert_eq!(error.expected, "String");
+    assert_eq!(error.actual, "Integer");
+}
+
+#[test]
+fn test_try_from_i64() {
+    let value: i64 = ParameterValue::from(42).try_into().unwrap();
+    assert_eq!(value, 42);
+
+    let error = i64::try_from(ParameterValue::from(1.5)).unwrap_err();
+    assert_eq!(error.expected, "Integer");
+    assert_eq!(error.actual, "Float");
+}
+
+#[test]
+fn test_try_from_f64() {
+    let value: f64 = ParameterValue::from(1.5).try_into().unwrap();
+    assert_eq!(value, 1.5);
+
+    let error = f64::try_from(ParameterValue::from(true)).unwrap_err();
+    assert_eq!(error.expected, "Float");
+    assert_eq!(error.actual, "Boolean");
+}
+
+#[test]
+fn test_try_from_bool() {
+    let value: bool = ParameterValue::from(true).try_into().unwrap();
+    assert!(value);
+
+    let error = bool::try_from(ParameterValue::Null).unwrap_err();
+    assert_eq!(error.expected, "Boolean");
+    assert_eq!(error.actual, "Null");
+    assert_eq!(error.to_string(), "expected Boolean, found Null");
+}
+
+fn user_parameters() -> ParameterValue {
+    let mut user = HashMap::new();
+    user.insert("name".to_string(), ParameterValue::from("alice"));
+    user.insert(
+        "roles".to_string(),
+        ParameterValue::from(vec!["admin", "dev"]),
+    );
+
+    let mut root = HashMap::new();
+    root.insert("user".to_string(), ParameterValue::Object(user));
+    ParameterValue::Object(root)
+}
+
+#[test]
+fn test_chained_indexing() {
+    let params = user_parameters();
+
+    assert_eq!(params["user"]["name"], ParameterValue::from("alice"));
+    assert_eq!(params["user"]["roles"][1], ParameterValue::from("dev"));
+}
+
+#[test]
+fn test_get_and_get_index() {
+    let params = user_parameters();
+
+    assert_eq!(
+        params
+            .get("user")
+            .and_then(|user| user.get("roles"))
+            .and_then(|roles| roles.get_index(0)),
+        Some(&ParameterValue::from("admin"))
+    );
+    assert_eq!(params.get("missing"), None);
+    assert_eq!(params["user"]["roles"].get_index(5), None);
+    assert_eq!(params.get_index(0), None);
+}
+
+#[test]
+#[should_panic(expected = "no entry found for key `email`")]
+fn test_index_missing_key_panics() {
+    let params = user_parameters();
+    let _ = &params["user"]["email"];
+}
+
+#[test]
+fn test_merge_nested_objects() {
+    let mut base = param_object! {
+        "user" => param_object! { "name" => "alice", "role" => "dev" },
+        "request" => "GET /users",
+    };
+    let other = param_object! {
+        "user" => param_object! { "role" => "admin", "id" => 42 },
+        "tenant" => "acme",
+    };
+
+    base.merge(other);
+
+    assert_eq!(
+        base,
+        param_object! {
+            "user" => param_object! { "name" => "alice", "role" => "admin", "id" => 42 },
+            "request" => "GET /users",
+            "tenant" => "acme",
+        }
+    );
+}
+
+#[test]
+fn test_merge_replaces_scalars_and_arrays() {
+    let mut base = param_object! {
+        "count" => 1,
+        "tags" => param_array!["a", "b"],
+    };
+
+    base.merge(param_object! {
+        "count" => param_object! { "value" => 2 },
+        "tags" => param_array!["c"],
+    });
+
+    assert_eq!(base["count"], param_object! { "value" => 2 });
+    assert_eq!(base["tags"], param_array!["c"]);
+
+    let mut scalar = ParameterValue::from("old");
+    scalar.merge(ParameterValue::from(7));
+    assert_eq!(scalar, ParameterValue::from(7));
+}
+
+#[test]
+fn test_display_quotes_nested_strings_with_delimiters() {
+    let value = param_object! { "name,role" => param_array!["a,b", "c"] };
+
+    assert_eq!(value.to_string(), r#"{"name,role": ["a,b", c]}"#);
+    assert_eq!(ParameterValue::from("a,b").to_string(), "a,b");
+}
+
+#[test]
+fn test_coerce_numbers() {
+    let mut value = param_object! {
+        "count" => "42",
+        "ratio" => "12.5",
+        "name" => "abc",
+        "items" => param_array!["-7", "1e3", "NaN", "inf", ""],
+    };
+
+    value.coerce_numbers();
+
+    assert_eq!(value["count"], ParameterValue::Integer(42));
+    assert_eq!(value["ratio"], ParameterValue::Float(12.5));
+    assert_eq!(value["name"], ParameterValue::from("abc"));
+    assert_eq!(
+        value["items"],
+        param_array![
+            ParameterValue::Integer(-7),
+            ParameterValue::Float(1000.0),
+            "NaN",
+            "inf",
+            ""
+        ]
+    );
+}
+
+#[test]
+fn test_approx_eq_floats() {
+    let a = ParameterValue::Float(0.1 + 0.2);
+    let b = ParameterValue::Float(0.3);
+
+    assert_ne!(a, b);
+    assert!(a.approx_eq(&b, 1e-9));
+    assert!(!ParameterValue::Float(0.3).approx_eq(&ParameterValue::Float(0.31), 1e-9));
+}
+
+#[test]
+fn test_approx_eq_nested() {
+    let a = param_object! {
+        "ratio" => 0.1 + 0.2,
+        "samples" => param_array![1.0 / 3.0, "label"],
+    };
+    let b = param_object! {
+        "ratio" => 0.3,
+        "samples" => param_array![0.333_333_333_3, "label"],
+    };
+    let c = param_object! {
+        "ratio" => 0.3,
+        "samples" => param_array![0.333_333_333_3, "other"],
+    };
+
+    assert!(a.approx_eq(&b, 1e-9));
+    assert!(!a.approx_eq(&b, 1e-12));
+    assert!(!a.approx_eq(&c, 1e-9));
+}
+
+#[test]
+fn test_approx_eq_exact_for_other_variants() {
+    assert!(ParameterValue::Integer(1).approx_eq(&ParameterValue::Integer(1), 0.5));
+    assert!(!ParameterValue::Integer(1).approx_eq(&ParameterValue::Float(1.0), 0.5));
+    assert!(!ParameterValue::from("a").approx_eq(&ParameterValue::from("b"), 0.5));
+}
+
+#[test]
+fn test_parse_recognized_forms() {
+    assert_eq!(ParameterValue::parse("true"), ParameterValue::Boolean(true));
+    assert_eq!(
+        ParameterValue::parse("false"),
+        ParameterValue::Boolean(false)
+    );
+    assert_eq!(ParameterValue::parse("null"), ParameterValue::Null);
+    assert_eq!(ParameterValue::parse("42"), ParameterValue::Integer(42));
+    assert_eq!(ParameterValue::parse("-7"), ParameterValue::Integer(-7));
+    assert_eq!(ParameterValue::parse("2.75"), ParameterValue::Float(2.75));
+}
+
+#[test]
+fn test_parse_falls_back_to_string() {
+    for input in ["hello", "True", "NaN", "inf", "", "42abc", "[1, 2]"] {
+        assert_eq!(
+            ParameterValue::parse(input),
+            ParameterValue::String(input.to_string())
+        );
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_from_json_str() {
+    assert_eq!(
+        ParameterValue::from_json_str(r#"[1, "two", null]"#).unwrap(),
+        param_array![1, "two", ParameterValue::Null]
+    );
+    assert_eq!(
+        ParameterValue::from_json_str(r#"{"min": 3, "strict": true}"#).unwrap(),
+        param_object! { "min" => 3, "strict" => true }
+    );
+    assert_eq!(
+        ParameterValue::from_json_str("2.5").unwrap(),
+        ParameterValue::Float(2.5)
+    );
+    assert!(ParameterValue::from_json_str("{not json").is_err());
+}
+
+#[test]
+fn test_bytes_from_vec_and_slice() {
+    let bytes: &[u8] = &[0xde, 0xad, 0xbe, 0xef];
+
+    assert_eq!(
+        ParameterValue::from(bytes.to_vec()),
+        ParameterValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef])
+    );
+    assert_eq!(
+        ParameterValue::from(bytes),
+        ParameterValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef])
+    );
+}
+
+#[test]
+fn test_bytes_display() {
+    assert_eq!(
+        ParameterValue::from(b"hello".to_vec()).to_string(),
+        "base64:aGVsbG8="
+    );
+    assert_eq!(
+        ParameterValue::from(b"hi!".to_vec()).to_string(),
+        "base64:aGkh"
+    );
+    assert_eq!(
+        ParameterValue::from(b"h".to_vec()).to_string(),
+        "base64:aA=="
+    );
+    assert_eq!(ParameterValue::Bytes(Vec::new()).to_string(), "base64:");
+}
+
+#[test]
+fn test_bytes_serialize_as_base64() {
+    let value = param_object! { "signature" => b"hello".to_vec() };
+
+    assert_eq!(
+        serde_json::to_string(&value).unwrap(),
+        r#"{"signature":"aGVsbG8="}"#
+    );
+    assert_eq!(format!("{:#}", value), r#"{"signature": "aGVsbG8="}"#);
+}
+
+#[test]
+fn test_bytes_deserialize_as_string() {
+    let value: ParameterValue = serde_json::from_str(r#""aGVsbG8=""#).unwrap();
+
+    assert_eq!(value, ParameterValue::String("aGVsbG8=".to_string()));
+}
+
+#[test]
+fn test_transform_uppercases_strings() {
+    let value = param_object! {
+        "name" => "alice",
+        "tags" => param_array!["admin", 7, param_object! { "role" => "owner" }],
+        "active" => true
+    };
+
+    let transformed = value.transform(&mut |value| match value {
+        ParameterValue::String(s) => ParameterValue::String(s.to_uppercase()),
+        other => other,
+    });
+
+    assert_eq!(
+        transformed,
+        param_object! {
+            "name" => "ALICE",
+            "tags" => param_array!["ADMIN", 7, param_object! { "role" => "OWNER" }],
+            "active" => true
+        }
+    );
+}
+
+#[test]
+fn test_transform_visits_children_before_parent() {
+    let value = param_array![1, param_array![2, 3]];
+
+    let mut visited = Vec::new();
+    value.transform(&mut |value| {
+        visited.push(value.to_string());
+        value
+    });
+
+    assert_eq!(visited, ["1", "2", "3", "[2, 3]", "[1, [2, 3]]"]);
+}
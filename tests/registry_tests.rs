@@ -0,0 +1,109 @@
+#[cfg(feature = "json")]
+use axum::response::IntoResponse;
+use axum_service_errors::{
+    ErrorRegistry, PlainTextResponseBuilder, RegistryError, ResponseBuilder, ServiceError,
+};
+
+#[cfg(feature = "json")]
+const CATALOG: &str = r#"[
+    {"code": 1001, "name": "VALIDATION_ERROR", "status": 400, "message": "Invalid {0}"},
+    {"code": 2001, "name": "NOT_FOUND", "status": 404, "message": "Resource not found"}
+]"#;
+
+#[cfg(feature = "json")]
+#[test]
+fn test_load_from_json() {
+    let registry = ErrorRegistry::load_from_json(CATALOG).unwrap();
+
+    let error = ServiceError::from_registry(&registry, 1001).unwrap();
+    assert_eq!(error.code, 1001);
+    assert_eq!(error.name, "VALIDATION_ERROR");
+    assert_eq!(error.http_status, 400);
+    assert_eq!(error.message, "Invalid {0}");
+
+    let response = error.bind("email").into_response();
+    assert_eq!(response.status(), 400);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_from_registry_unknown_code() {
+    let registry = ErrorRegistry::load_from_json(CATALOG).unwrap();
+
+    assert!(ServiceError::from_registry(&registry, 9999).is_none());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_load_from_json_duplicate_code() {
+    let catalog = r#"[
+        {"code": 1001, "name": "VALIDATION_ERROR", "status": 400, "message": "Invalid input"},
+        {"code": 1001, "name": "OTHER_ERROR", "status": 500, "message": "Other"}
+    ]"#;
+
+    let result = ErrorRegistry::load_from_json(catalog);
+    assert_eq!(result.unwrap_err(), RegistryError::DuplicateCode(1001));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_load_from_json_invalid_catalog() {
+    let result = ErrorRegistry::load_from_json(r#"[{"code": 1001}]"#);
+    assert!(matches!(result, Err(RegistryError::InvalidCatalog(_))));
+}
+
+#[test]
+fn test_register_and_get() {
+    let mut registry = ErrorRegistry::new();
+    registry
+        .register(ServiceError::new(
+            2001,
+            "NOT_FOUND",
+            404,
+            "User {0} not found",
+        ))
+        .unwrap();
+
+    let error = registry.get(2001).unwrap();
+    assert_eq!(error.name, "NOT_FOUND");
+    assert_eq!(error.http_status, 404);
+    assert!(registry.get(2002).is_none());
+}
+
+#[test]
+fn test_register_duplicate_code() {
+    let mut registry = ErrorRegistry::new();
+    registry
+        .register(ServiceError::new(2001, "NOT_FOUND", 404, "User not found"))
+        .unwrap();
+
+    let result = registry.register(ServiceError::new(2001, "GONE", 410, "User deleted"));
+    assert_eq!(result.unwrap_err(), RegistryError::DuplicateCode(2001));
+    assert_eq!(registry.get(2001).unwrap().name, "NOT_FOUND");
+}
+
+#[test]
+fn test_registered_error_can_be_customized() {
+    let mut registry = ErrorRegistry::new();
+    registry
+        .register(ServiceError::new(
+            2001,
+            "NOT_FOUND",
+            404,
+            "User {0} not found",
+        ))
+        .unwrap();
+
+    let error = registry
+        .get(2001)
+        .unwrap()
+        .bind("alice")
+        .parameter("user_id", 42);
+    let (body, _) = PlainTextResponseBuilder::new().build(&error);
+
+    assert_eq!(
+        body,
+        "Error 2001: NOT_FOUND - User alice not found (Parameters: {user_id: 42})"
+    );
+    assert_eq!(registry.get(2001).unwrap().arguments.len(), 0);
+}
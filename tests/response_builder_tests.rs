@@ -1,8 +1,14 @@
 use axum::response::IntoResponse;
-use axum_service_errors::{PlainTextResponseBuilder, ResponseBuilder, ServiceError};
+use axum_service_errors::{
+    BytesEncoding, Charset, FallbackResponseBuilder, HtmlResponseBuilder, ParameterValue,
+    PlainTextResponseBuilder, ResponseBuilder, ServiceError, StatusRoutingResponseBuilder,
+    param_array, param_object,
+};
 
+#[cfg(feature = "yaml")]
+use axum_service_errors::YamlResponseBuilder;
 #[cfg(feature = "json")]
-use axum_service_errors::JsonResponseBuilder;
+use axum_service_errors::{DebugResponseBuilder, EnvelopeResponseBuilder, JsonResponseBuilder};
 
 #[test]
 fn test_default_plain_text_response() {
@@ -19,7 +25,7 @@ fn test_plain_text_response_builder() {
     let builder = PlainTextResponseBuilder::new();
 
     let (body, content_type) = builder.build(&error);
-    assert_eq!(content_type, "text/plain");
+    assert_eq!(content_type, "text/plain; charset=utf-8");
     assert!(body.contains("Error 1001"));
     assert!(body.contains("VALIDATION_ERROR"));
     assert!(body.contains("Invalid input"));
@@ -34,7 +40,7 @@ fn test_plain_text_with_parameters() {
     let builder = PlainTextResponseBuilder::new();
     let (body, content_type) = builder.build(&error);
 
-    assert_eq!(content_type, "text/plain");
+    assert_eq!(content_type, "text/plain; charset=utf-8");
     assert!(body.contains("Parameters:"));
 }
 
@@ -67,7 +73,7 @@ fn test_json_response_builder() {
     let builder = JsonResponseBuilder::new();
     let (body, content_type) = builder.build(&error);
 
-    assert_eq!(content_type, "application/json");
+    assert_eq!(content_type, "application/json; charset=utf-8");
     assert!(body.contains("\"code\":1001"));
     assert!(body.contains("\"name\":\"VALIDATION_ERROR\""));
     assert!(body.contains("\"message\":\"Invalid input\""));
@@ -122,3 +128,1136 @@ fn test_clone_loses_response_builder() {
 
     assert_eq!(response1.status(), response2.status());
 }
+
+#[test]
+fn test_plain_text_redacts_parameters() {
+    let error = ServiceError::new(1001, "AUTH_ERROR", 401, "Invalid credentials")
+        .parameter("user", "alice")
+        .parameter("password", "hunter2")
+        .redact(&["password"]);
+
+    let builder = PlainTextResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(body.contains("password: [REDACTED]"));
+    assert!(body.contains("user: alice"));
+    assert!(!body.contains("hunter2"));
+
+    let parameters = error.parameters.as_ref().unwrap();
+    assert_eq!(parameters["password"], ParameterValue::from("hunter2"));
+}
+
+#[test]
+fn test_redaction_applies_to_nested_objects() {
+    let error = ServiceError::new(1001, "AUTH_ERROR", 401, "Invalid credentials")
+        .parameter(
+            "request",
+            param_object! {
+                "token" => "secret-token",
+                "scopes" => param_array![param_object! { "token" => "nested-token" }],
+            },
+        )
+        .redact(&["token"]);
+
+    let builder = PlainTextResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(!body.contains("secret-token"));
+    assert!(!body.contains("nested-token"));
+    assert_eq!(body.matches("[REDACTED]").count(), 2);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_redacts_parameters() {
+    let error = ServiceError::new(1001, "AUTH_ERROR", 401, "Invalid credentials")
+        .parameter("token", "secret-token")
+        .redact(&["token"]);
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(body.contains("\"token\":\"[REDACTED]\""));
+    assert!(!body.contains("secret-token"));
+
+    let parameters = error.parameters.as_ref().unwrap();
+    assert_eq!(parameters["token"], ParameterValue::from("secret-token"));
+}
+
+#[test]
+fn test_plain_text_with_trace_id() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("field", "email")
+        .trace_id("4bf92f3577b34da6");
+
+    let builder = PlainTextResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(body.ends_with(" (trace: 4bf92f3577b34da6)"));
+}
+
+#[test]
+fn test_plain_text_without_trace_id() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let builder = PlainTextResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(body, "Error 1001: VALIDATION_ERROR - Invalid input");
+}
+
+#[test]
+fn test_clone_keeps_trace_id() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .trace_id("4bf92f3577b34da6");
+
+    assert_eq!(error.clone().trace_id.as_deref(), Some("4bf92f3577b34da6"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_with_trace_id() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .trace_id("4bf92f3577b34da6");
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(body.contains("\"trace_id\":\"4bf92f3577b34da6\""));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_without_trace_id() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(!body.contains("trace_id"));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_yaml_response_builder() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0}")
+        .bind("input")
+        .parameter("field", "email")
+        .parameter("limits", param_object! { "min" => 3, "max" => 64 })
+        .parameter("allowed", param_array!["a", "b"]);
+
+    let builder = YamlResponseBuilder::new();
+    let (body, content_type) = builder.build(&error);
+
+    assert_eq!(content_type, "application/yaml; charset=utf-8");
+
+    let value: serde_yaml::Value = serde_yaml::from_str(&body).unwrap();
+    assert_eq!(value["code"], serde_yaml::Value::from(1001));
+    assert_eq!(value["message"], serde_yaml::Value::from("Invalid input"));
+    assert_eq!(
+        value["parameters"]["limits"]["max"],
+        serde_yaml::Value::from(64)
+    );
+    assert!(value["parameters"]["allowed"].is_sequence());
+}
+
+#[test]
+fn test_html_response_builder() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User {0} not found").bind("alice");
+
+    let builder = HtmlResponseBuilder::new();
+    let (body, content_type) = builder.build(&error);
+
+    assert_eq!(content_type, "text/html; charset=utf-8");
+    assert!(body.starts_with("<!DOCTYPE html>"));
+    assert!(body.contains("<title>404 NOT_FOUND</title>"));
+    assert!(body.contains("<p>User alice not found</p>"));
+}
+
+#[test]
+fn test_html_response_builder_with_title() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "Not found");
+
+    let builder = HtmlResponseBuilder::new().with_title("Oops");
+    let (body, _) = builder.build(&error);
+
+    assert!(body.contains("<title>Oops</title>"));
+}
+
+#[test]
+fn test_html_response_builder_escapes_content() {
+    let error = ServiceError::new(
+        1001,
+        "VALIDATION_ERROR",
+        400,
+        "Invalid <script>alert(1)</script>",
+    )
+    .parameter("<b>field</b>", "\"quoted\" & 'single'");
+
+    let builder = HtmlResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(!body.contains("<script>"));
+    assert!(body.contains("Invalid &lt;script&gt;alert(1)&lt;/script&gt;"));
+    assert!(body.contains("<dt>&lt;b&gt;field&lt;/b&gt;</dt>"));
+    assert!(body.contains("<dd>&quot;quoted&quot; &amp; &#x27;single&#x27;</dd>"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_envelope_response_builder() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0}")
+        .bind("input")
+        .parameter("field", "email");
+
+    let builder = EnvelopeResponseBuilder::new();
+    let (body, content_type) = builder.build(&error);
+
+    assert_eq!(content_type, "application/json; charset=utf-8");
+    assert!(body.starts_with("{\"success\":false,\"error\":{"));
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["success"], false);
+    assert_eq!(json["error"]["code"], 1001);
+    assert_eq!(json["error"]["name"], "VALIDATION_ERROR");
+    assert_eq!(json["error"]["message"], "Invalid input");
+    assert_eq!(json["error"]["parameters"]["field"], "email");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_envelope_response_builder_renamed_keys() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let builder = EnvelopeResponseBuilder::new()
+        .with_success_key("ok")
+        .with_error_key("failure");
+    let (body, _) = builder.build(&error);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["ok"], false);
+    assert_eq!(json["failure"]["code"], 1001);
+    assert!(json.get("success").is_none());
+    assert!(json.get("error").is_none());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_includes_severity() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .severity(axum_service_errors::Severity::Warning);
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(body.contains("\"severity\":\"warning\""));
+}
+
+#[test]
+fn test_plain_text_with_doc_url() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+        .doc_url("https://docs.example.com/errors/2001");
+
+    let builder = PlainTextResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(
+        body,
+        "Error 2001: NOT_FOUND - User not found (see: https://docs.example.com/errors/2001)"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_with_doc_url() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+        .doc_url("https://docs.example.com/errors/2001");
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(body.contains("\"type\":\"https://docs.example.com/errors/2001\""));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_without_doc_url() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(!body.contains("\"type\""));
+}
+
+#[test]
+fn test_plain_text_with_template() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User {0} not found").bind("alice");
+
+    let builder = PlainTextResponseBuilder::new().with_template("[{code}] {name}: {message}");
+    let (body, content_type) = builder.build(&error);
+
+    assert_eq!(body, "[2001] NOT_FOUND: User alice not found");
+    assert_eq!(content_type, "text/plain; charset=utf-8");
+}
+
+#[test]
+fn test_plain_text_template_with_parameters() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found").parameter("id", 42);
+
+    let builder = PlainTextResponseBuilder::new().with_template("{name} {parameters}");
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(body, "NOT_FOUND {id: 42}");
+}
+
+#[test]
+fn test_plain_text_template_keeps_unknown_tokens() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    let builder = PlainTextResponseBuilder::new().with_template("{code} {status} {parameters}{");
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(body, "2001 {status} {");
+}
+
+#[test]
+fn test_plain_text_template_with_unterminated_brace() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let builder = PlainTextResponseBuilder::new().with_template("Error {code} costs {");
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(body, "Error 1001 costs {");
+}
+
+#[test]
+fn test_plain_text_template_with_stray_brace() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let builder = PlainTextResponseBuilder::new().with_template("{x {code} {{name}}");
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(body, "{x 1001 {VALIDATION_ERROR}");
+}
+
+#[test]
+fn test_plain_text_shows_only_public_parameters() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("field", "email")
+        .parameter("user_id", 42)
+        .parameter("query", "SELECT 1")
+        .public_parameters(&["field"]);
+
+    let builder = PlainTextResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(
+        body,
+        "Error 1001: VALIDATION_ERROR - Invalid input (Parameters: {field: email})"
+    );
+    assert_eq!(error.parameters.as_ref().unwrap().len(), 3);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_shows_only_public_parameters() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("field", "email")
+        .parameter("user_id", 42)
+        .parameter("query", "SELECT 1")
+        .public_parameters(&["field"]);
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["parameters"], serde_json::json!({ "field": "email" }));
+}
+
+#[test]
+fn test_client_safe_hides_server_error_message() {
+    let error = ServiceError::new(5001, "DATABASE_ERROR", 500, "Connection to {0} refused")
+        .bind("db-primary:5432")
+        .client_safe();
+
+    let builder = PlainTextResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(
+        body,
+        "Error 5001: DATABASE_ERROR - An internal error occurred"
+    );
+    assert_eq!(error.message, "Connection to {0} refused");
+}
+
+#[test]
+fn test_client_safe_keeps_client_error_message() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0}")
+        .bind("email")
+        .client_safe();
+
+    let builder = PlainTextResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(body, "Error 1001: VALIDATION_ERROR - Invalid email");
+}
+
+#[cfg(feature = "json")]
+#[derive(Debug)]
+struct QueryError {
+    source: std::io::Error,
+}
+
+#[cfg(feature = "json")]
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "query failed")
+    }
+}
+
+#[cfg(feature = "json")]
+impl std::error::Error for QueryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_debug_builder_includes_causes_in_order() {
+    let error = ServiceError::new(5001, "DATABASE_ERROR", 500, "Database unavailable").with_source(
+        QueryError {
+            source: std::io::Error::other("connection refused"),
+        },
+    );
+
+    let builder = DebugResponseBuilder::new();
+    let (body, content_type) = builder.build(&error);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["code"], 5001);
+    assert_eq!(json["message"], "Database unavailable");
+    assert_eq!(
+        json["causes"],
+        serde_json::json!(["query failed", "connection refused"])
+    );
+    assert_eq!(content_type, "application/json; charset=utf-8");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_debug_builder_without_source() {
+    let error = ServiceError::new(5001, "DATABASE_ERROR", 500, "Database unavailable");
+
+    let builder = DebugResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["causes"], serde_json::json!([]));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_with_detail() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+        .bind("alice")
+        .with_detail("No user named {0} exists in this workspace");
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["message"], "User not found");
+    assert_eq!(
+        json["detail"],
+        "No user named alice exists in this workspace"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_without_detail() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(!body.contains("\"detail\""));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_client_safe_omits_server_error_detail() {
+    let error = ServiceError::new(5001, "DATABASE_ERROR", 500, "Database unavailable")
+        .with_detail("Connection to db-primary:5432 refused")
+        .client_safe();
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(!body.contains("db-primary"));
+}
+
+#[test]
+fn test_html_with_detail() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+        .bind("<alice>")
+        .with_detail("No user named {0}");
+
+    let builder = HtmlResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(body.contains("<p>No user named &lt;alice&gt;</p>"));
+}
+
+#[test]
+fn test_status_routing_builder() {
+    let builder = StatusRoutingResponseBuilder::new()
+        .route(4, HtmlResponseBuilder::new())
+        .route(
+            5,
+            PlainTextResponseBuilder::new().with_template("{code} {name}"),
+        );
+
+    let not_found = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+    let (_, content_type) = builder.build(&not_found);
+    assert_eq!(content_type, "text/html; charset=utf-8");
+
+    let internal = ServiceError::new(5001, "DATABASE_ERROR", 500, "Database unavailable")
+        .parameter("host", "db-primary");
+    let (body, content_type) = builder.build(&internal);
+    assert_eq!(body, "5001 DATABASE_ERROR");
+    assert_eq!(content_type, "text/plain; charset=utf-8");
+}
+
+#[test]
+fn test_status_routing_builder_fallback() {
+    let builder = StatusRoutingResponseBuilder::new()
+        .route(5, HtmlResponseBuilder::new())
+        .with_fallback(PlainTextResponseBuilder::new().with_template("{name}"));
+
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(body, "NOT_FOUND");
+}
+
+fn write_to_string(builder: &dyn ResponseBuilder, error: &ServiceError) -> (String, &'static str) {
+    let mut body = String::new();
+    let content_type = builder.write_to(error, &mut body).unwrap();
+    (body, content_type)
+}
+
+#[test]
+fn test_status_routing_builder_delegates_content_type_and_write_to() {
+    let builder = StatusRoutingResponseBuilder::new()
+        .route(4, HtmlResponseBuilder::new())
+        .with_fallback(PlainTextResponseBuilder::new().with_charset(None));
+
+    assert_eq!(builder.content_type(), "text/plain");
+
+    let not_found = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+    let (body, content_type) = write_to_string(&builder, &not_found);
+    assert_eq!(content_type, "text/html; charset=utf-8");
+    assert_eq!(body, HtmlResponseBuilder::new().build(&not_found).0);
+
+    let internal = ServiceError::new(5001, "DATABASE_ERROR", 500, "Database unavailable");
+    let (body, content_type) = write_to_string(&builder, &internal);
+    assert_eq!(content_type, "text/plain");
+    assert_eq!(body, "Error 5001: DATABASE_ERROR - Database unavailable");
+}
+
+#[test]
+fn test_plain_text_write_to_matches_build() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0}")
+        .bind("email")
+        .parameter("field", "email")
+        .parameter(
+            "request",
+            param_object! { "tags" => param_array!["a", "b"] },
+        )
+        .trace_id("4bf92f3577b34da6");
+
+    let builder = PlainTextResponseBuilder::new();
+    for _ in 0..100 {
+        assert_eq!(write_to_string(&builder, &error), builder.build(&error));
+    }
+
+    let builder = builder.with_template("[{code}] {message} {parameters}");
+    assert_eq!(write_to_string(&builder, &error), builder.build(&error));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_write_to_matches_build() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Ungültige {0} – \"{1}\"")
+        .bind("Eingabe")
+        .bind("😀")
+        .parameter("field", "email")
+        .parameter(
+            "request",
+            param_object! { "tags" => param_array!["a", "b"] },
+        );
+
+    let builder = JsonResponseBuilder::new();
+    for _ in 0..100 {
+        assert_eq!(write_to_string(&builder, &error), builder.build(&error));
+    }
+}
+
+#[test]
+fn test_default_write_to_uses_build() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    let builder = HtmlResponseBuilder::new();
+    assert_eq!(write_to_string(&builder, &error), builder.build(&error));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_with_timestamp() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let mut error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+    error.timestamp = Some(UNIX_EPOCH + Duration::from_millis(1_709_209_845_123));
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["timestamp"], "2024-02-29T12:30:45.123Z");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_with_current_timestamp() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found").with_timestamp();
+    let cloned = error.clone();
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&cloned);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let timestamp = json["timestamp"].as_str().unwrap();
+    assert_eq!(timestamp.len(), "2024-02-29T12:30:45.123Z".len());
+    assert_eq!(&timestamp[4..5], "-");
+    assert_eq!(&timestamp[10..11], "T");
+    assert!(timestamp.ends_with('Z'));
+    assert_eq!(cloned.timestamp, error.timestamp);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_without_timestamp() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    let builder = JsonResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert!(!body.contains("\"timestamp\""));
+}
+
+#[test]
+fn test_plain_text_quotes_keys_with_delimiters() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("a, b: c", "x, y");
+
+    let builder = PlainTextResponseBuilder::new();
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(
+        body,
+        r#"Error 1001: VALIDATION_ERROR - Invalid input (Parameters: {"a, b: c": "x, y"})"#
+    );
+}
+
+fn reverse_ordered_error() -> ServiceError<'static> {
+    ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("zeta", 3)
+        .parameter("mid", param_object! { "b" => 2, "a" => 1 })
+        .parameter("alpha", 1)
+}
+
+#[test]
+fn test_plain_text_sorted_keys() {
+    let error = reverse_ordered_error();
+
+    let builder = PlainTextResponseBuilder::new().sorted_keys(true);
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(
+        body,
+        "Error 1001: VALIDATION_ERROR - Invalid input (Parameters: {alpha: 1, mid: {a: 1, b: 2}, zeta: 3})"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_sorted_keys() {
+    let error = reverse_ordered_error();
+
+    let builder = JsonResponseBuilder::new().sorted_keys(true);
+    let (body, _) = builder.build(&error);
+
+    assert!(body.contains(r#""parameters":{"alpha":1,"mid":{"a":1,"b":2},"zeta":3}"#));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_content_type() {
+    assert_eq!(
+        JsonResponseBuilder::new().content_type(),
+        "application/json; charset=utf-8"
+    );
+}
+
+#[test]
+fn test_builtin_content_types() {
+    assert_eq!(
+        PlainTextResponseBuilder::new().content_type(),
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(
+        HtmlResponseBuilder::new().content_type(),
+        "text/html; charset=utf-8"
+    );
+}
+
+#[test]
+fn test_default_content_type_builds_response() {
+    #[derive(Debug)]
+    struct CsvBuilder;
+
+    impl ResponseBuilder for CsvBuilder {
+        fn build(&self, error: &ServiceError) -> (String, &'static str) {
+            (format!("{},{}", error.code, error.name), "text/csv")
+        }
+    }
+
+    assert_eq!(CsvBuilder.content_type(), "text/csv");
+}
+
+#[test]
+fn test_plain_text_typed_values() {
+    let builder = PlainTextResponseBuilder::new().with_typed_values(true);
+
+    let boolean =
+        ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input").parameter("flag", true);
+    let string =
+        ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input").parameter("flag", "true");
+    let null = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("flag", ParameterValue::Null);
+
+    assert_eq!(
+        builder.build(&boolean).0,
+        "Error 1001: VALIDATION_ERROR - Invalid input (Parameters: {flag: (bool)true})"
+    );
+    assert_eq!(
+        builder.build(&string).0,
+        "Error 1001: VALIDATION_ERROR - Invalid input (Parameters: {flag: true})"
+    );
+    assert_eq!(
+        builder.build(&null).0,
+        "Error 1001: VALIDATION_ERROR - Invalid input (Parameters: {flag: (null)})"
+    );
+}
+
+#[test]
+fn test_plain_text_untyped_values_by_default() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("flags", param_array![true, ParameterValue::Null]);
+
+    let (body, _) = PlainTextResponseBuilder::new().build(&error);
+
+    assert_eq!(
+        body,
+        "Error 1001: VALIDATION_ERROR - Invalid input (Parameters: {flags: [true, null]})"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_nested_service_error_parameter() {
+    let underlying = ServiceError::new(3001, "DB_TIMEOUT", 504, "Query timed out after {0}s")
+        .bind(30)
+        .parameter("table", "users");
+    let error = ServiceError::new(5001, "INTERNAL_ERROR", 500, "Lookup failed")
+        .parameter("underlying", underlying);
+
+    let (body, _) = JsonResponseBuilder::new().build(&error);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let nested = &json["parameters"]["underlying"];
+    assert_eq!(nested["code"], 3001);
+    assert_eq!(nested["name"], "DB_TIMEOUT");
+    assert_eq!(nested["message"], "Query timed out after 30s");
+    assert_eq!(nested["parameters"]["table"], "users");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_parameters_output() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("field", "email");
+
+    let (body, _) = JsonResponseBuilder::new().build(&error);
+
+    assert_eq!(
+        body,
+        r#"{"code":1001,"name":"VALIDATION_ERROR","message":"Invalid input","parameters":{"field":"email"},"severity":"error"}"#
+    );
+
+    let (body, _) = JsonResponseBuilder::new().build(&error.redact(&["field"]));
+
+    assert_eq!(
+        body,
+        r#"{"code":1001,"name":"VALIDATION_ERROR","message":"Invalid input","parameters":{"field":"[REDACTED]"},"severity":"error"}"#
+    );
+}
+
+#[derive(Debug)]
+struct TenantResponseBuilder;
+
+impl ResponseBuilder for TenantResponseBuilder {
+    fn build(&self, error: &ServiceError) -> (String, &'static str) {
+        (format!("{} (tenant: unknown)", error.name), "text/plain")
+    }
+
+    fn build_with_parts(
+        &self,
+        error: &ServiceError,
+        parts: &axum::http::request::Parts,
+    ) -> (String, &'static str) {
+        let tenant = parts
+            .headers
+            .get("x-tenant-id")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("unknown");
+        (format!("{} (tenant: {})", error.name, tenant), "text/plain")
+    }
+}
+
+#[tokio::test]
+async fn test_into_response_with_parts() {
+    let (parts, _) = axum::http::Request::builder()
+        .header("x-tenant-id", "acme")
+        .body(())
+        .unwrap()
+        .into_parts();
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+        .with_response_builder(StatusRoutingResponseBuilder::new().route(4, TenantResponseBuilder));
+
+    let response = error.into_response_with_parts(&parts);
+
+    assert_eq!(response.status(), 404);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body, "NOT_FOUND (tenant: acme)");
+}
+
+#[test]
+fn test_build_with_parts_defaults_to_build() {
+    let (parts, _) = axum::http::Request::builder()
+        .body(())
+        .unwrap()
+        .into_parts();
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    let builder = PlainTextResponseBuilder::new();
+
+    assert_eq!(
+        builder.build_with_parts(&error, &parts),
+        builder.build(&error)
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_rename_field() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("field", "email");
+
+    let builder = JsonResponseBuilder::new()
+        .rename_field("code", "errorCode")
+        .rename_field("name", "errorName");
+    let (body, _) = builder.build(&error);
+
+    assert_eq!(
+        body,
+        r#"{"errorCode":1001,"errorName":"VALIDATION_ERROR","message":"Invalid input","parameters":{"field":"email"},"severity":"error"}"#
+    );
+}
+
+#[derive(Debug)]
+struct FailingResponseBuilder;
+
+impl ResponseBuilder for FailingResponseBuilder {
+    fn build(&self, _error: &ServiceError) -> (String, &'static str) {
+        (String::new(), "application/x-failing")
+    }
+
+    fn write_to(
+        &self,
+        _error: &ServiceError,
+        _w: &mut dyn std::fmt::Write,
+    ) -> Result<&'static str, std::fmt::Error> {
+        Err(std::fmt::Error)
+    }
+}
+
+#[test]
+fn test_fallback_builder_skips_failing_builder() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let builder = FallbackResponseBuilder::new(vec![
+        Box::new(FailingResponseBuilder),
+        Box::new(HtmlResponseBuilder::new()),
+        Box::new(PlainTextResponseBuilder::new()),
+    ]);
+    let (body, content_type) = builder.build(&error);
+
+    assert_eq!(content_type, "text/html; charset=utf-8");
+    assert!(body.starts_with("<!DOCTYPE html>"));
+}
+
+#[test]
+fn test_fallback_builder_uses_plain_text_when_all_fail() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let builder = FallbackResponseBuilder::new(vec![Box::new(FailingResponseBuilder)]);
+    let (body, content_type) = builder.build(&error);
+
+    assert_eq!(content_type, "text/plain; charset=utf-8");
+    assert_eq!(body, "Error 1001: VALIDATION_ERROR - Invalid input");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_fallback_builder_prefers_first_builder() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let builder = FallbackResponseBuilder::new(vec![
+        Box::new(JsonResponseBuilder::new()),
+        Box::new(PlainTextResponseBuilder::new()),
+    ]);
+    let (_, content_type) = builder.build(&error);
+
+    assert_eq!(content_type, "application/json; charset=utf-8");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_slug() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found").slug("user.not_found");
+
+    let (body, _) = JsonResponseBuilder::new().build(&error);
+
+    assert_eq!(
+        body,
+        r#"{"code":2001,"name":"NOT_FOUND","slug":"user.not_found","message":"User not found","severity":"error"}"#
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_slug_absent_by_default() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    let (body, _) = JsonResponseBuilder::new().build(&error);
+
+    assert!(!body.contains("slug"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_lossless_numbers() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("id", 9_007_199_254_740_993_i64)
+        .parameter(
+            "limits",
+            param_object! { "ratio" => 0.1, "names" => param_array!["a"] },
+        );
+
+    let builder = JsonResponseBuilder::new().with_lossless_numbers(true);
+    let (body, _) = builder.build(&error);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["parameters"]["id"], "9007199254740993");
+    assert_eq!(json["parameters"]["limits"]["ratio"], "0.1");
+    assert_eq!(json["parameters"]["limits"]["names"][0], "a");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_numbers_by_default() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("id", 9_007_199_254_740_993_i64);
+
+    let (body, _) = JsonResponseBuilder::new().build(&error);
+
+    assert!(body.contains(r#""id":9007199254740993"#));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_const_error_with_static_parameters() {
+    const QUOTA_EXCEEDED: ServiceError<'static> =
+        ServiceError::new(4290, "QUOTA_EXCEEDED", 429, "Quota exceeded")
+            .with_static_parameters(&[("plan", "free"), ("window", "1h")]);
+
+    let error = QUOTA_EXCEEDED.parameter("window", "24h");
+    let (body, _) = JsonResponseBuilder::new().build(&error);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["parameters"]["plan"], "free");
+    assert_eq!(json["parameters"]["window"], "24h");
+
+    let (body, _) = PlainTextResponseBuilder::new().build(&QUOTA_EXCEEDED);
+    assert!(body.contains("plan: free"));
+}
+
+#[test]
+fn test_internal_note_is_not_rendered() {
+    let error = ServiceError::new(5001, "DATABASE_ERROR", 500, "Service unavailable")
+        .internal_note("replica lag over 30s");
+
+    let (body, _) = PlainTextResponseBuilder::new().build(&error);
+    assert!(!body.contains("replica lag"));
+
+    #[cfg(feature = "json")]
+    {
+        let (body, _) = JsonResponseBuilder::new().build(&error);
+        assert!(!body.contains("replica lag"));
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_code_setter() {
+    let base_error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    let error = base_error.code(7001);
+    let (body, _) = JsonResponseBuilder::new().build(&error);
+
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["code"], 7001);
+    assert_eq!(json["name"], "NOT_FOUND");
+    assert_eq!(error.http_status, 404);
+}
+
+#[test]
+fn test_plain_text_charset() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let (_, content_type) = PlainTextResponseBuilder::new().build(&error);
+    assert_eq!(content_type, "text/plain; charset=utf-8");
+
+    let builder = PlainTextResponseBuilder::new().with_charset(None);
+    assert_eq!(builder.build(&error).1, "text/plain");
+    assert_eq!(builder.content_type(), "text/plain");
+
+    let builder = PlainTextResponseBuilder::new().with_charset(Some(Charset::Utf8));
+    assert_eq!(builder.content_type(), "text/plain; charset=utf-8");
+}
+
+#[test]
+fn test_html_charset() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let builder = HtmlResponseBuilder::new().with_charset(None);
+    assert_eq!(builder.build(&error).1, "text/html");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_charset() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let (_, content_type) = JsonResponseBuilder::new().build(&error);
+    assert_eq!(content_type, "application/json; charset=utf-8");
+
+    let builder = JsonResponseBuilder::new().with_charset(None);
+    assert_eq!(builder.build(&error).1, "application/json");
+
+    let builder = EnvelopeResponseBuilder::new().with_charset(None);
+    assert_eq!(builder.build(&error).1, "application/json");
+
+    let builder = DebugResponseBuilder::new().with_charset(None);
+    assert_eq!(builder.build(&error).1, "application/json");
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_yaml_charset() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    let (_, content_type) = YamlResponseBuilder::new().build(&error);
+    assert_eq!(content_type, "application/yaml; charset=utf-8");
+
+    let builder = YamlResponseBuilder::new().with_charset(None);
+    assert_eq!(builder.build(&error).1, "application/yaml");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_pretty_output() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("field", "email");
+
+    let (pretty, _) = JsonResponseBuilder::new().pretty(true).build(&error);
+    assert!(pretty.contains("\n  \"code\": 1001"));
+    assert!(pretty.contains("\n    \"field\": \"email\""));
+
+    let (compact, _) = JsonResponseBuilder::new().build(&error);
+    assert!(!compact.contains('\n'));
+    assert!(!compact.contains("  "));
+
+    let pretty: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+    let compact: serde_json::Value = serde_json::from_str(&compact).unwrap();
+    assert_eq!(pretty, compact);
+}
+
+#[test]
+fn test_plain_text_bytes_as_hex() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("digest", ParameterValue::Bytes(b"hello".to_vec()));
+
+    let (hex, _) = PlainTextResponseBuilder::new().build(&error);
+    let (base64, _) = PlainTextResponseBuilder::new()
+        .with_bytes_encoding(BytesEncoding::Base64)
+        .build(&error);
+
+    assert_eq!(
+        hex,
+        "Error 1001: VALIDATION_ERROR - Invalid input (Parameters: {digest: hex:68656c6c6f})"
+    );
+    assert_eq!(
+        base64,
+        "Error 1001: VALIDATION_ERROR - Invalid input (Parameters: {digest: base64:aGVsbG8=})"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_bytes_as_base64() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("digest", ParameterValue::Bytes(b"hello".to_vec()))
+        .parameter(
+            "nested",
+            param_array![ParameterValue::Bytes(vec![0xde, 0xad])],
+        );
+
+    let (base64, _) = JsonResponseBuilder::new().build(&error);
+    let (hex, _) = JsonResponseBuilder::new()
+        .with_bytes_encoding(BytesEncoding::Hex)
+        .build(&error);
+
+    let base64: serde_json::Value = serde_json::from_str(&base64).unwrap();
+    assert_eq!(base64["parameters"]["digest"], "aGVsbG8=");
+    assert_eq!(base64["parameters"]["nested"][0], "3q0=");
+    let hex: serde_json::Value = serde_json::from_str(&hex).unwrap();
+    assert_eq!(hex["parameters"]["digest"], "68656c6c6f");
+    assert_eq!(hex["parameters"]["nested"][0], "dead");
+}
@@ -0,0 +1,90 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use axum::{http::header, response::IntoResponse};
+use axum_service_errors::{RetryAfter, ServiceError};
+
+#[test]
+fn test_www_authenticate_header() {
+    let error = ServiceError::new(4010, "UNAUTHORIZED", 401, "Token expired")
+        .www_authenticate(r#"Bearer realm="api", error="invalid_token""#);
+
+    let response = error.into_response();
+
+    assert_eq!(response.status(), 401);
+    assert_eq!(
+        response.headers()[header::WWW_AUTHENTICATE],
+        r#"Bearer realm="api", error="invalid_token""#
+    );
+}
+
+#[test]
+fn test_www_authenticate_header_absent_by_default() {
+    let error = ServiceError::new(4010, "UNAUTHORIZED", 401, "Token expired");
+
+    let response = error.into_response();
+
+    assert!(!response.headers().contains_key(header::WWW_AUTHENTICATE));
+}
+
+#[test]
+fn test_cache_control_header() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+        .cache_control("public, max-age=60")
+        .no_store_for_5xx();
+
+    let response = error.into_response();
+
+    assert_eq!(
+        response.headers()[header::CACHE_CONTROL],
+        "public, max-age=60"
+    );
+}
+
+#[test]
+fn test_cache_control_no_store_for_5xx() {
+    let error = ServiceError::new(5001, "INTERNAL_ERROR", 500, "Database unavailable")
+        .cache_control("public, max-age=60")
+        .no_store_for_5xx();
+
+    let response = error.into_response();
+
+    assert_eq!(response.headers()[header::CACHE_CONTROL], "no-store");
+}
+
+#[test]
+fn test_cache_control_header_absent_by_default() {
+    let error = ServiceError::new(5001, "INTERNAL_ERROR", 500, "Database unavailable");
+
+    let response = error.into_response();
+
+    assert!(!response.headers().contains_key(header::CACHE_CONTROL));
+}
+
+#[test]
+fn test_rate_limited_retry_after_seconds() {
+    let response = ServiceError::rate_limited(RetryAfter::Seconds(120)).into_response();
+
+    assert_eq!(response.status(), 429);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "120");
+}
+
+#[test]
+fn test_rate_limited_retry_after_http_date() {
+    let retry = RetryAfter::HttpDate(UNIX_EPOCH + Duration::from_secs(784_111_777));
+    let response = ServiceError::rate_limited(retry).into_response();
+
+    assert_eq!(response.status(), 429);
+    assert_eq!(
+        response.headers()[header::RETRY_AFTER],
+        "Sun, 06 Nov 1994 08:49:37 GMT"
+    );
+}
+
+#[test]
+fn test_retry_after_http_date_formatting() {
+    let leap_day = RetryAfter::HttpDate(UNIX_EPOCH + Duration::from_millis(1_709_208_000_500));
+    assert_eq!(leap_day.to_string(), "Thu, 29 Feb 2024 12:00:00 GMT");
+
+    let epoch = RetryAfter::HttpDate(UNIX_EPOCH);
+    assert_eq!(epoch.to_string(), "Thu, 01 Jan 1970 00:00:00 GMT");
+}
@@ -0,0 +1,49 @@
+use axum_service_errors::{
+    ErrorRegistry, FallbackResponseBuilder, HtmlResponseBuilder, LocalizedResponseBuilder,
+    MessageCatalog, ParameterValue, PlainTextResponseBuilder, ServiceError, ServiceErrorLayer,
+    StatusRoutingResponseBuilder,
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_service_error_is_send_sync() {
+    assert_send_sync::<ServiceError<'static>>();
+    assert_send_sync::<ParameterValue>();
+}
+
+#[test]
+fn test_response_builders_are_send_sync() {
+    assert_send_sync::<PlainTextResponseBuilder>();
+    assert_send_sync::<HtmlResponseBuilder>();
+    assert_send_sync::<LocalizedResponseBuilder>();
+    assert_send_sync::<StatusRoutingResponseBuilder>();
+    assert_send_sync::<FallbackResponseBuilder>();
+    assert_send_sync::<ServiceErrorLayer>();
+    assert_send_sync::<MessageCatalog>();
+    assert_send_sync::<ErrorRegistry>();
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_response_builders_are_send_sync() {
+    use axum_service_errors::{DebugResponseBuilder, EnvelopeResponseBuilder, JsonResponseBuilder};
+
+    assert_send_sync::<JsonResponseBuilder>();
+    assert_send_sync::<EnvelopeResponseBuilder>();
+    assert_send_sync::<DebugResponseBuilder>();
+}
+
+#[tokio::test]
+async fn test_service_error_held_across_await() {
+    let error = ServiceError::new(5001, "DATABASE_ERROR", 500, "Database unavailable")
+        .with_source(std::io::Error::other("connection refused"))
+        .with_lazy_message(|| "Database unavailable".to_string());
+
+    let handle = tokio::spawn(async move {
+        tokio::task::yield_now().await;
+        error.code
+    });
+
+    assert_eq!(handle.await.unwrap(), 5001);
+}
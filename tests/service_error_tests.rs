@@ -0,0 +1,964 @@
+use std::borrow::Cow;
+
+use axum::{http::StatusCode, response::IntoResponse};
+use axum_service_errors::{
+    Category, InvalidNamespace, InvalidStatus, NAMESPACE_SIZE, ParameterValue,
+    PlainTextResponseBuilder, ResponseBuilder, ServiceError, ServiceResult, Severity,
+    UnresolvedPlaceholder, ValidationIssue, namespace_of, param_array, param_object,
+};
+
+#[test]
+fn test_assert_valid_accepts_well_formed_error() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0} for field {1}")
+        .bind("value")
+        .bind("email");
+
+    assert_eq!(error.assert_valid(), Ok(()));
+}
+
+#[test]
+fn test_assert_valid_reports_all_issues() {
+    let error = ServiceError::new(1001, "", 42, "Invalid {0} for field {1}").bind("value");
+
+    let issues = error.assert_valid().unwrap_err();
+    assert_eq!(
+        issues,
+        vec![
+            ValidationIssue::InvalidStatus(42),
+            ValidationIssue::EmptyName,
+            ValidationIssue::ArgumentCountMismatch {
+                placeholders: 2,
+                arguments: 1,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_assert_valid_rejects_non_error_status() {
+    let error = ServiceError::new(1001, "OK", 200, "Everything is fine");
+
+    assert_eq!(
+        error.assert_valid(),
+        Err(vec![ValidationIssue::NonErrorStatus(200)])
+    );
+}
+
+#[test]
+fn test_try_new_accepts_valid_status() {
+    let error = ServiceError::try_new(1001, "VALIDATION_ERROR", 400, "Invalid input").unwrap();
+
+    assert_eq!(error.http_status, 400);
+}
+
+#[test]
+fn test_try_new_rejects_invalid_status() {
+    let result = ServiceError::try_new(1001, "VALIDATION_ERROR", 999, "Invalid input");
+    assert_eq!(result.unwrap_err(), InvalidStatus(999));
+
+    let result = ServiceError::try_new(1001, "VALIDATION_ERROR", 42, "Invalid input");
+    assert_eq!(result.unwrap_err(), InvalidStatus(42));
+}
+
+#[test]
+fn test_merge_context() {
+    let cause = ServiceError::new(3001, "DB_ERROR", 500, "Query {0} failed on {1}")
+        .bind("select_user")
+        .bind("replica-2")
+        .parameter("table", "users")
+        .parameter("retryable", true);
+
+    let error = ServiceError::new(1001, "USER_LOOKUP_FAILED", 500, "Lookup failed for {0}")
+        .bind("alice")
+        .parameter("table", "accounts")
+        .merge_context(&cause);
+
+    assert_eq!(
+        error.arguments,
+        vec![
+            ParameterValue::from("alice"),
+            ParameterValue::from("select_user"),
+            ParameterValue::from("replica-2"),
+        ]
+    );
+
+    let parameters = error.parameters.unwrap();
+    assert_eq!(parameters.len(), 2);
+    assert_eq!(parameters["table"], ParameterValue::from("accounts"));
+    assert_eq!(parameters["retryable"], ParameterValue::from(true));
+}
+
+#[test]
+fn test_severity_defaults_to_error() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    assert_eq!(error.severity, Severity::Error);
+    assert_eq!(error.clone().severity, Severity::Error);
+}
+
+#[test]
+fn test_severity_can_be_set() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .severity(Severity::Warning);
+
+    assert_eq!(error.severity, Severity::Warning);
+    assert_eq!(error.clone().severity, Severity::Warning);
+}
+
+fn rendered_message(error: &ServiceError) -> String {
+    let (body, _) = PlainTextResponseBuilder::new().build(error);
+    let prefix = format!("Error {}: {} - ", error.code, error.name);
+    body.strip_prefix(&prefix).unwrap().to_string()
+}
+
+#[test]
+fn test_format_zero_padding() {
+    let error =
+        ServiceError::new(4290, "RATE_LIMITED", 429, "Retry in {0:02} seconds").bind_value(3);
+
+    assert_eq!(rendered_message(&error), "Retry in 03 seconds");
+}
+
+#[test]
+fn test_format_float_precision() {
+    let error = ServiceError::new(
+        4020,
+        "INSUFFICIENT_FUNDS",
+        402,
+        "Balance is {0:.2}, need {1:06.1}",
+    )
+    .bind_value(12.3456)
+    .bind_value(42);
+
+    assert_eq!(rendered_message(&error), "Balance is 12.35, need 0042.0");
+}
+
+#[test]
+fn test_format_spec_on_non_numeric_argument() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0:05}").bind("email");
+
+    assert_eq!(rendered_message(&error), "Invalid email");
+}
+
+#[test]
+fn test_format_leaves_unresolved_placeholders() {
+    let error =
+        ServiceError::new(1001, "VALIDATION_ERROR", 400, "{0} {1} {name} {x:2}").bind("{1}");
+
+    assert_eq!(rendered_message(&error), "{1} {1} {name} {x:2}");
+}
+
+#[test]
+fn test_bind_value_keeps_argument_type() {
+    let error = ServiceError::new(4290, "RATE_LIMITED", 429, "Retry in {0} seconds ({1})")
+        .bind_value(42)
+        .bind_value(true);
+
+    assert_eq!(
+        error.arguments,
+        vec![ParameterValue::Integer(42), ParameterValue::Boolean(true)]
+    );
+    assert_eq!(rendered_message(&error), "Retry in 42 seconds (true)");
+}
+
+#[test]
+fn test_bind_value_unsigned_and_char_arguments() {
+    let items = ["a", "b", "c"];
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "{0} {1} {2} {3}")
+        .bind_value(items.len())
+        .bind_value(7u64)
+        .bind_value(u64::MAX)
+        .bind_value('x');
+
+    assert_eq!(
+        error.arguments,
+        vec![
+            ParameterValue::Integer(3),
+            ParameterValue::Integer(7),
+            ParameterValue::String("18446744073709551615".to_string()),
+            ParameterValue::String("x".to_string()),
+        ]
+    );
+    assert_eq!(rendered_message(&error), "3 7 18446744073709551615 x");
+}
+
+#[test]
+fn test_bind_accepts_display_types() {
+    let name = String::from("alice");
+    let alias: &&str = &"al";
+    let address = std::net::IpAddr::from([127, 0, 0, 1]);
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "{0} {1} {2} {3} {4} {5}")
+        .bind(&name)
+        .bind(std::borrow::Cow::Borrowed("cow"))
+        .bind(1u8)
+        .bind(alias)
+        .bind(address)
+        .bind(42);
+
+    assert_eq!(
+        error.arguments,
+        vec![
+            ParameterValue::from("alice"),
+            ParameterValue::from("cow"),
+            ParameterValue::from("1"),
+            ParameterValue::from("al"),
+            ParameterValue::from("127.0.0.1"),
+            ParameterValue::from("42"),
+        ]
+    );
+    assert_eq!(rendered_message(&error), "alice cow 1 al 127.0.0.1 42");
+}
+
+#[test]
+fn test_bind_all_unsigned_arguments() {
+    let sizes: Vec<usize> = vec![1, 2];
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "{0}-{1}").bind_all(sizes);
+
+    assert_eq!(rendered_message(&error), "1-2");
+}
+
+#[test]
+fn test_equal_errors() {
+    let a = ServiceError::new(2001, "NOT_FOUND", 404, "User {0} not found")
+        .bind("alice")
+        .parameter("id", 42)
+        .parameter("score", 1.5);
+    let b = ServiceError::new(2001, "NOT_FOUND", 404, "User {0} not found")
+        .bind("alice")
+        .parameter("score", 1.5)
+        .parameter("id", 42);
+
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_errors_with_different_codes_are_not_equal() {
+    let a = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+    let b = ServiceError::new(2002, "NOT_FOUND", 404, "User not found");
+
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_equal_errors_hash_equally() {
+    use std::collections::HashSet;
+
+    let make = || {
+        ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+            .parameter("id", 42)
+            .parameter("score", 1.5)
+            .parameter("zero", -0.0)
+    };
+
+    let mut errors = HashSet::new();
+    errors.insert(make());
+    errors.insert(make().parameter("zero", 0.0));
+    errors.insert(ServiceError::new(2002, "NOT_FOUND", 404, "User not found"));
+
+    assert_eq!(errors.len(), 2);
+    assert!(errors.contains(&make()));
+}
+
+#[test]
+fn test_error_with_nan_parameter_equals_itself() {
+    use std::collections::HashSet;
+
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "Score {0}")
+        .bind_value(f64::NAN)
+        .parameter("scores", param_array![f64::NAN, 1.5]);
+
+    assert_eq!(error, error.clone());
+    let mut errors = HashSet::new();
+    errors.insert(error.clone());
+    assert!(errors.contains(&error));
+}
+
+#[test]
+fn test_static_parameters_compare_as_rendered() {
+    let a = ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+        .with_static_parameters(&[("service", "users")]);
+    let b =
+        ServiceError::new(2001, "NOT_FOUND", 404, "User not found").parameter("service", "users");
+    let c = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn test_map_message() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User {0} not found")
+        .bind("alice")
+        .map_message(|message| message.to_uppercase());
+
+    assert_eq!(error.message, "USER ALICE NOT FOUND");
+    assert!(error.arguments.is_empty());
+
+    let error = error.bind("bob");
+    assert_eq!(rendered_message(&error), "USER ALICE NOT FOUND");
+}
+
+#[test]
+fn test_from_status() {
+    let error = ServiceError::from_status(StatusCode::NOT_FOUND);
+
+    assert_eq!(error.code, 404);
+    assert_eq!(error.http_status, 404);
+    assert_eq!(error.name, "NOT_FOUND");
+    assert_eq!(error.message, "Not Found");
+}
+
+#[test]
+fn test_from_status_with_punctuated_reason() {
+    let error = ServiceError::from_status(StatusCode::IM_A_TEAPOT);
+
+    assert_eq!(error.name, "IM_A_TEAPOT");
+    assert_eq!(error.message, "I'm a teapot");
+}
+
+#[test]
+fn test_is_client_and_server_error() {
+    let not_found = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+    let unavailable = ServiceError::new(5030, "UNAVAILABLE", 503, "Try again later");
+    let redirect = ServiceError::new(3010, "MOVED", 301, "Moved");
+
+    assert!(not_found.is_client_error());
+    assert!(!not_found.is_server_error());
+    assert!(!unavailable.is_client_error());
+    assert!(unavailable.is_server_error());
+    assert!(!redirect.is_client_error());
+    assert!(!redirect.is_server_error());
+}
+
+#[test]
+fn test_owned() {
+    let resource = String::from("invoice");
+    let error = ServiceError::owned(
+        2001,
+        format!("{}_NOT_FOUND", resource.to_uppercase()),
+        404,
+        format!("No {} with id {}", resource, 42),
+    );
+    drop(resource);
+
+    let (body, _) = PlainTextResponseBuilder::new().build(&error);
+    assert_eq!(
+        body,
+        "Error 2001: INVOICE_NOT_FOUND - No invoice with id 42"
+    );
+}
+
+#[test]
+fn test_from_status_and_message_tuple() {
+    let error: ServiceError = (404, "user not found").into();
+
+    assert_eq!(error.code, 404);
+    assert_eq!(error.http_status, 404);
+    assert_eq!(error.name, "NOT_FOUND");
+    assert_eq!(error.message, "user not found");
+}
+
+#[test]
+fn test_from_tuple_with_invalid_status() {
+    let error = ServiceError::from((42, "odd"));
+
+    assert_eq!(error.code, 42);
+    assert_eq!(error.name, "UNKNOWN");
+}
+
+#[test]
+fn test_bind_all() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "{0}-{1}").bind_all(["a", "b"]);
+
+    assert_eq!(rendered_message(&error), "a-b");
+}
+
+#[test]
+fn test_bind_all_accepts_display_items() {
+    let name = String::from("alice");
+    let other = String::from("bob");
+    let address = std::net::IpAddr::from([127, 0, 0, 1]);
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "{0}-{1} {2}")
+        .bind_all(vec![&name, &other])
+        .bind_all([address]);
+
+    assert_eq!(rendered_message(&error), "alice-bob 127.0.0.1");
+}
+
+#[test]
+fn test_bind_all_appends_after_bind() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "{0} {1} {2}")
+        .bind(1)
+        .bind_all(vec![2, 3]);
+
+    assert_eq!(rendered_message(&error), "1 2 3");
+}
+
+#[test]
+fn test_parameter_accessors() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("field", "email");
+
+    assert_eq!(error.parameters_ref().map(|params| params.len()), Some(1));
+    assert_eq!(
+        error.parameter_get("field"),
+        Some(&ParameterValue::from("email"))
+    );
+    assert_eq!(error.parameter_get("missing"), None);
+}
+
+#[test]
+fn test_parameter_accessors_exclude_static_parameters() {
+    let error = ServiceError::new(4290, "QUOTA_EXCEEDED", 429, "Quota exceeded")
+        .with_static_parameters(&[("plan", "free"), ("limit", "100")])
+        .parameter("limit", 200);
+
+    assert_eq!(error.parameters_ref().map(|params| params.len()), Some(1));
+    assert_eq!(
+        error.parameter_get("limit"),
+        Some(&ParameterValue::from(200))
+    );
+    assert_eq!(error.parameter_get("plan"), None);
+    assert_eq!(
+        error.static_parameters(),
+        &[("plan", "free"), ("limit", "100")]
+    );
+}
+
+#[test]
+fn test_parameter_accessors_without_parameters() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    assert!(error.parameters_ref().is_none());
+    assert_eq!(error.parameter_get("field"), None);
+}
+
+#[test]
+fn test_push_parameter_creates_array() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .push_parameter("rejected", "email");
+
+    assert_eq!(
+        error.parameter_get("rejected"),
+        Some(&param_array!["email"])
+    );
+}
+
+#[test]
+fn test_push_parameter_appends_to_array() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("rejected", param_array!["email"])
+        .push_parameter("rejected", "phone")
+        .push_parameter("rejected", "zip");
+
+    assert_eq!(
+        error.parameter_get("rejected"),
+        Some(&param_array!["email", "phone", "zip"])
+    );
+}
+
+#[test]
+fn test_push_parameter_wraps_existing_scalar() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("rejected", "email")
+        .push_parameter("rejected", "phone");
+
+    assert_eq!(
+        error.parameter_get("rejected"),
+        Some(&param_array!["email", "phone"])
+    );
+}
+
+#[test]
+fn test_parameter_if() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter_if(true, "field", "email")
+        .parameter_if(false, "retry", true);
+
+    assert_eq!(
+        error.parameter_get("field"),
+        Some(&ParameterValue::from("email"))
+    );
+    assert_eq!(error.parameter_get("retry"), None);
+}
+
+#[test]
+fn test_parameter_some() {
+    let tenant: Option<&str> = None;
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter_some("limit", Some(64))
+        .parameter_some("tenant", tenant);
+
+    assert_eq!(
+        error.parameter_get("limit"),
+        Some(&ParameterValue::from(64))
+    );
+    assert_eq!(error.parameter_get("tenant"), None);
+}
+
+#[test]
+fn test_conditional_parameters_absent_leave_parameters_unset() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter_if(false, "field", "email")
+        .parameter_some("limit", None::<i64>);
+
+    assert!(error.parameters_ref().is_none());
+}
+
+#[test]
+fn test_format_message_borrows_without_placeholders() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found").bind("ignored");
+
+    let message = error.format_message();
+
+    assert!(matches!(message, Cow::Borrowed(_)));
+    assert_eq!(message.as_ptr(), error.message.as_ptr());
+}
+
+#[test]
+fn test_format_message_borrows_without_arguments() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User {0} not found");
+
+    let message = error.format_message();
+
+    assert_eq!(message.as_ptr(), error.message.as_ptr());
+    assert_eq!(message, "User {0} not found");
+}
+
+#[test]
+fn test_format_message_with_arguments() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User {0} not found").bind("alice");
+
+    let message = error.format_message();
+
+    assert!(matches!(message, Cow::Owned(_)));
+    assert_eq!(message, "User alice not found");
+}
+
+#[test]
+fn test_lenient_formatting_keeps_unresolved_placeholders() {
+    let error =
+        ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0} for {missing}").bind("value");
+
+    assert_eq!(
+        error.try_format_message(),
+        Ok(Cow::from("Invalid value for {missing}"))
+    );
+    assert_eq!(rendered_message(&error), "Invalid value for {missing}");
+}
+
+#[test]
+fn test_strict_formatting_reports_unresolved_placeholders() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0} for {missing}")
+        .bind("value")
+        .strict_formatting(true);
+
+    assert_eq!(
+        error.try_format_message(),
+        Err(UnresolvedPlaceholder("{missing}".to_string()))
+    );
+    assert_eq!(rendered_message(&error), "Invalid value for {missing}");
+
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0} in {1}")
+        .bind("value")
+        .strict_formatting(true);
+    assert_eq!(
+        error.try_format_message(),
+        Err(UnresolvedPlaceholder("{1}".to_string()))
+    );
+}
+
+#[test]
+fn test_strict_formatting_ignores_non_placeholder_braces() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Expected {} or { \"a\": 1 }")
+        .strict_formatting(true);
+
+    assert_eq!(
+        error.try_format_message(),
+        Ok(Cow::from("Expected {} or { \"a\": 1 }"))
+    );
+}
+
+#[test]
+fn test_map_message_formats_detail() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found")
+        .bind("alice")
+        .with_detail("No user named {0}")
+        .map_message(|message| message.to_uppercase());
+
+    assert_eq!(error.detail.as_deref(), Some("No user named alice"));
+}
+
+#[test]
+fn test_category_default_status() {
+    assert_eq!(Category::Validation.default_status(), 400);
+    assert_eq!(Category::Auth.default_status(), 401);
+    assert_eq!(Category::NotFound.default_status(), 404);
+    assert_eq!(Category::Conflict.default_status(), 409);
+    assert_eq!(Category::Internal.default_status(), 500);
+}
+
+#[test]
+fn test_error_of_category() {
+    const NOT_FOUND: ServiceError =
+        ServiceError::of(Category::NotFound, 2001, "NOT_FOUND", "User not found");
+
+    assert_eq!(NOT_FOUND.http_status, 404);
+    assert_eq!(NOT_FOUND.category, Some(Category::NotFound));
+}
+
+#[test]
+fn test_category_round_trip() {
+    let error = ServiceError::new(4090, "DUPLICATE_EMAIL", 409, "Email already registered")
+        .category(Category::Conflict);
+
+    let json = serde_json::to_string(&error).unwrap();
+    assert!(json.contains("\"category\":\"conflict\""));
+
+    let parsed: ServiceError = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.category, Some(Category::Conflict));
+
+    let json = serde_json::to_string(&ServiceError::new(1001, "INVALID_INPUT", 400, "x")).unwrap();
+    assert!(!json.contains("category"));
+    let without: ServiceError = serde_json::from_str(&json).unwrap();
+    assert_eq!(without.category, None);
+}
+
+#[test]
+fn test_lazy_message_evaluated_on_render() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .bind("email")
+        .with_lazy_message(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            "Invalid {0} (computed)".to_string()
+        });
+
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    assert_eq!(rendered_message(&error), "Invalid email (computed)");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_lazy_message_evaluated_once_per_response() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .bind("email")
+        .strict_formatting(true)
+        .with_lazy_message(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            "Invalid {0} for {1}".to_string()
+        });
+
+    let response = error.into_response();
+
+    assert_eq!(response.status(), 400);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_lazy_message_not_evaluated_when_discarded() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .with_lazy_message(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            "expensive".to_string()
+        });
+    drop(error.clone());
+    drop(error);
+
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_log_line() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0} \"{1}\"")
+        .bind("email")
+        .bind("a@b")
+        .parameter("field", "email")
+        .parameter("reason", "bad format")
+        .parameter("attempts", 3);
+
+    assert_eq!(
+        error.log_line(),
+        r#"code=1001 name=VALIDATION_ERROR status=400 msg="Invalid email \"a@b\"" attempts=3 field=email reason="bad format""#
+    );
+}
+
+#[test]
+fn test_log_line_without_parameters() {
+    let error = ServiceError::new(2001, "NOT_FOUND", 404, "User not found");
+
+    assert_eq!(
+        error.log_line(),
+        r#"code=2001 name=NOT_FOUND status=404 msg="User not found""#
+    );
+}
+
+#[test]
+fn test_log_line_includes_internal_note() {
+    let error = ServiceError::new(5001, "DATABASE_ERROR", 500, "Service unavailable")
+        .internal_note("replica lag over 30s")
+        .parameter("shard", 4);
+
+    assert_eq!(
+        error.log_line(),
+        r#"code=5001 name=DATABASE_ERROR status=500 msg="Service unavailable" note="replica lag over 30s" shard=4"#
+    );
+}
+
+#[test]
+fn test_with_parameters_object() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("field", "name")
+        .with_parameters_object(param_object! {
+            "field" => "email",
+            "allowed" => param_array!["a", "b"],
+        });
+
+    let parameters = error.parameters.as_ref().unwrap();
+    assert_eq!(parameters.len(), 2);
+    assert_eq!(parameters["field"], ParameterValue::from("email"));
+    assert_eq!(parameters["allowed"], param_array!["a", "b"]);
+}
+
+#[test]
+fn test_with_parameters_object_ignores_non_objects() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .with_parameters_object(param_array!["a"]);
+
+    assert!(error.parameters.is_none());
+}
+
+#[test]
+fn test_default_error() {
+    let error = ServiceError::default();
+
+    assert_eq!(error.code, 5000);
+    assert_eq!(error.name, "INTERNAL_ERROR");
+    assert_eq!(error.http_status, 500);
+    assert_eq!(error.message, "");
+    assert!(error.parameters.is_none());
+}
+
+#[test]
+fn test_default_error_with_builder_chain() {
+    let error = ServiceError::default().parameter("field", "email");
+
+    assert_eq!(error.http_status, 500);
+    assert_eq!(
+        error.parameter_get("field"),
+        Some(&ParameterValue::from("email"))
+    );
+}
+
+#[test]
+fn test_truncate_long_string_parameter() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("input", "é".repeat(100))
+        .truncate_parameters(9);
+
+    assert_eq!(
+        error.parameter_get("input"),
+        Some(&ParameterValue::from("éééé… (truncated)"))
+    );
+}
+
+#[test]
+fn test_truncate_large_bytes_parameter() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("payload", vec![0xab_u8; 1 << 20])
+        .truncate_parameters(16);
+
+    assert_eq!(
+        error.parameter_get("payload"),
+        Some(&ParameterValue::Bytes(vec![0xab; 16]))
+    );
+}
+
+#[test]
+fn test_truncate_large_array_parameter() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("ids", (0..1000).collect::<Vec<i32>>())
+        .truncate_parameters(64);
+
+    let Some(ParameterValue::Array(ids)) = error.parameter_get("ids") else {
+        panic!("expected ids array");
+    };
+    assert!(ids.len() < 1000);
+    assert_eq!(ids[0], ParameterValue::from(0));
+    assert_eq!(ids.last(), Some(&ParameterValue::from("… (truncated)")));
+}
+
+#[test]
+fn test_truncate_parameters_drops_entries_in_key_order() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("a", "x".repeat(10))
+        .parameter("b", "y".repeat(10))
+        .parameter("c", "z".repeat(10))
+        .truncate_parameters(25);
+
+    let parameters = error.parameters_ref().unwrap();
+    assert_eq!(parameters.len(), 3);
+    assert!(parameters.contains_key("a"));
+    assert!(parameters.contains_key("b"));
+    assert_eq!(parameters.get("… (truncated)"), Some(&ParameterValue::Null));
+}
+
+#[test]
+fn test_truncate_parameters_keeps_small_values() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("field", "email")
+        .parameter("ids", vec![1, 2, 3]);
+    let truncated = error.clone().truncate_parameters(1024);
+
+    assert_eq!(truncated.parameters, error.parameters);
+}
+
+#[test]
+fn test_clear_and_set_parameters() {
+    use std::collections::HashMap;
+
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("field", "email")
+        .parameter("query", "SELECT 1")
+        .clear_parameters();
+
+    assert!(error.parameters.is_none());
+
+    let mut replacement = HashMap::new();
+    replacement.insert("field".to_string(), ParameterValue::from("name"));
+    let error = error
+        .parameter("query", "SELECT 2")
+        .set_parameters(replacement.clone());
+
+    assert_eq!(error.parameters, Some(replacement));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_into_json() {
+    let (status, axum::Json(body)) =
+        ServiceError::new(2001, "NOT_FOUND", 404, "User {0} not found")
+            .bind("alice")
+            .parameter("user", "alice")
+            .into_json();
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body.code, 2001);
+    assert_eq!(body.name, "NOT_FOUND");
+    assert_eq!(body.message, "User alice not found");
+    assert_eq!(
+        body.parameters().unwrap()["user"],
+        ParameterValue::String("alice".to_string())
+    );
+}
+
+#[test]
+fn test_with_namespace() {
+    let error =
+        ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input").with_namespace(42);
+
+    assert_eq!(error.code, 4_201_001);
+    assert_eq!(namespace_of(error.code), (42, 1001));
+}
+
+#[test]
+fn test_namespace_of_code_without_namespace() {
+    assert_eq!(namespace_of(1001), (0, 1001));
+    assert_eq!(namespace_of(NAMESPACE_SIZE - 1), (0, NAMESPACE_SIZE - 1));
+}
+
+#[test]
+fn test_try_with_namespace() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input");
+
+    assert_eq!(
+        error.clone().try_with_namespace(42).unwrap().code,
+        4_201_001
+    );
+    assert_eq!(
+        error.clone().try_with_namespace(u32::MAX).unwrap_err(),
+        InvalidNamespace::Overflow {
+            prefix: u32::MAX,
+            code: 1001
+        }
+    );
+    assert_eq!(
+        error.code(150_000).try_with_namespace(1).unwrap_err(),
+        InvalidNamespace::CodeOutOfRange(150_000)
+    );
+}
+
+#[test]
+#[should_panic(expected = "overflows")]
+fn test_with_namespace_overflow_panics() {
+    let _ =
+        ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input").with_namespace(u32::MAX);
+}
+
+// `ServiceError` exceeds clippy's `large-error-threshold`, as documented on
+// `ServiceResult`.
+#[allow(clippy::result_large_err)]
+fn find_user(id: u32) -> ServiceResult<&'static str> {
+    if id == 0 {
+        Err(ServiceError::new(
+            1001,
+            "VALIDATION_ERROR",
+            400,
+            "Invalid id",
+        ))?;
+    }
+    let message = format!("User {} not found", id);
+    let lookup: Result<&'static str, ServiceError<'_>> =
+        Err(ServiceError::new(2001, "NOT_FOUND", 404, &message));
+    let user = lookup.map_err(ServiceError::into_owned)?;
+    Ok(user)
+}
+
+#[test]
+fn test_service_result_with_question_mark() {
+    let error = find_user(0).unwrap_err();
+    assert_eq!(error.code, 1001);
+
+    let error = find_user(7).unwrap_err();
+    assert_eq!(error.code, 2001);
+    assert_eq!(error.message, "User 7 not found");
+}
+
+#[test]
+fn test_into_owned_keeps_fields() {
+    let name = String::from("NOT_FOUND");
+    let error = ServiceError::new(2001, &name, 404, "User not found")
+        .slug("user.not_found")
+        .with_detail("No such user")
+        .parameter("id", 7);
+
+    let owned: ServiceError<'static> = error.clone().into_owned();
+    drop(name);
+
+    assert!(matches!(owned.name, Cow::Owned(_)));
+    assert_eq!(owned.name, "NOT_FOUND");
+    assert_eq!(owned.slug.as_deref(), Some("user.not_found"));
+    assert_eq!(owned.detail.as_deref(), Some("No such user"));
+    assert_eq!(owned.parameter_get("id"), Some(&ParameterValue::Integer(7)));
+}
+
+#[test]
+#[should_panic(expected = "outside the namespace size")]
+fn test_with_namespace_rejects_code_outside_namespace() {
+    let _ = ServiceError::new(150_000, "VALIDATION_ERROR", 400, "Invalid input").with_namespace(1);
+}
@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+
+use axum::http::StatusCode;
+use axum_service_errors::{ServiceError, register_status_names};
+
+// The status names are global, so the whole sequence runs in one test.
+#[test]
+fn test_register_status_names() {
+    let error = ServiceError::from_status(StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error.name, "UNPROCESSABLE_ENTITY");
+
+    let names = HashMap::from([(422, "UNPROCESSABLE".to_string())]);
+    let previous = register_status_names(names);
+    assert!(previous.is_none());
+
+    let error = ServiceError::from_status(StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error.name, "UNPROCESSABLE");
+    assert_eq!(error.code, 422);
+    assert_eq!(error.message, "Unprocessable Entity");
+
+    let error = ServiceError::from_status(StatusCode::NOT_FOUND);
+    assert_eq!(error.name, "NOT_FOUND");
+
+    let previous = register_status_names(HashMap::new());
+    assert_eq!(previous.unwrap()[&422], "UNPROCESSABLE");
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "tracing")]
+
+use axum::response::IntoResponse;
+use axum_service_errors::{ServiceError, Severity};
+use tracing_test::traced_test;
+
+#[traced_test]
+#[test]
+fn test_into_response_emits_error_event() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid {0}")
+        .bind("email")
+        .parameter("field", "user.email");
+
+    let _ = error.into_response();
+
+    assert!(logs_contain("ERROR"));
+    assert!(logs_contain("code=1001"));
+    assert!(logs_contain("name=VALIDATION_ERROR"));
+    assert!(logs_contain("status=400"));
+    assert!(logs_contain(
+        r#"parameters={"field": String("user.email")}"#
+    ));
+    assert!(logs_contain("Invalid email"));
+}
+
+#[traced_test]
+#[test]
+fn test_severity_maps_to_log_level() {
+    let error =
+        ServiceError::new(1001, "INVALID_INPUT", 400, "Invalid input").severity(Severity::Warning);
+
+    let _ = error.into_response();
+
+    assert!(logs_contain("WARN"));
+    assert!(logs_contain("severity=warning"));
+    assert!(!logs_contain("ERROR"));
+}
+
+#[traced_test]
+#[test]
+fn test_event_records_typed_parameters() {
+    let error = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input")
+        .parameter("retries", 3)
+        .parameter("field", "email");
+
+    let _ = error.into_response();
+
+    assert!(logs_contain(
+        r#"parameters={"field": String("email"), "retries": Integer(3)}"#
+    ));
+}
+
+#[traced_test]
+#[test]
+fn test_event_without_parameters_omits_field() {
+    let _ = ServiceError::new(1001, "VALIDATION_ERROR", 400, "Invalid input").into_response();
+
+    assert!(logs_contain("code=1001"));
+    assert!(!logs_contain("parameters="));
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "validator")]
+
+use std::borrow::Cow;
+
+use axum_service_errors::{ParameterValue, ServiceError};
+use validator::{ValidationError, ValidationErrors};
+
+#[test]
+fn test_from_validation_errors() {
+    let mut errors = ValidationErrors::new();
+    errors.add(
+        "email",
+        ValidationError::new("email").with_message(Cow::Borrowed("must be a valid email")),
+    );
+    errors.add("age", ValidationError::new("range"));
+
+    let error: ServiceError<'static> = errors.into();
+
+    assert_eq!(error.code, 4220);
+    assert_eq!(error.http_status, 422);
+
+    let Some(ParameterValue::Object(fields)) = error.parameters.as_ref().unwrap().get("fields")
+    else {
+        panic!("expected fields object");
+    };
+    assert_eq!(fields.len(), 2);
+
+    let Some(ParameterValue::Array(email)) = fields.get("email") else {
+        panic!("expected email failures");
+    };
+    let ParameterValue::Object(failure) = &email[0] else {
+        panic!("expected failure object");
+    };
+    assert_eq!(failure.get("code"), Some(&ParameterValue::from("email")));
+    assert_eq!(
+        failure.get("message"),
+        Some(&ParameterValue::from("must be a valid email"))
+    );
+
+    let Some(ParameterValue::Array(age)) = fields.get("age") else {
+        panic!("expected age failures");
+    };
+    let ParameterValue::Object(failure) = &age[0] else {
+        panic!("expected failure object");
+    };
+    assert_eq!(failure.get("code"), Some(&ParameterValue::from("range")));
+    assert!(!failure.contains_key("message"));
+}